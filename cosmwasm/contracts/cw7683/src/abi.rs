@@ -0,0 +1,102 @@
+use cosmwasm_std::{StdError, StdResult, Uint128};
+use go_fast::helpers::left_pad_bytes;
+
+/// Minimal Solidity ABI encoder covering the types used by the ERC-7683
+/// structs.
+pub enum Token {
+    Word([u8; 32]),
+    Bytes(Vec<u8>),
+    Array(Vec<Token>),
+    Tuple(Vec<Token>),
+}
+
+impl Token {
+    pub fn uint(value: u64) -> Self {
+        Self::uint128(Uint128::from(value))
+    }
+
+    pub fn uint128(value: Uint128) -> Self {
+        let mut word = [0u8; 32];
+        word[16..].copy_from_slice(&value.to_be_bytes());
+        Self::Word(word)
+    }
+
+    /// Left pads `bz` to a single word, as Solidity does for addresses stored
+    /// in `bytes32` values. Errors if `bz` does not fit in a word.
+    pub fn bytes32(bz: &[u8]) -> StdResult<Self> {
+        if bz.len() > 32 {
+            return Err(StdError::generic_err(format!(
+                "bytes32 value is {} bytes long",
+                bz.len()
+            )));
+        }
+
+        let mut word = [0u8; 32];
+        word.copy_from_slice(&left_pad_bytes(bz.to_vec(), 32));
+        Ok(Self::Word(word))
+    }
+
+    fn is_dynamic(&self) -> bool {
+        match self {
+            Self::Word(_) => false,
+            Self::Bytes(_) | Self::Array(_) => true,
+            Self::Tuple(tokens) => tokens.iter().any(Token::is_dynamic),
+        }
+    }
+
+    /// Size of the token in its enclosing tuple's head. Static tuples are
+    /// encoded in place, everything else dynamic takes an offset word.
+    fn head_len(&self) -> usize {
+        match self {
+            Self::Tuple(tokens) if !self.is_dynamic() => tokens.iter().map(Token::head_len).sum(),
+            _ => 32,
+        }
+    }
+
+    fn encode_into(&self, bz: &mut Vec<u8>) {
+        match self {
+            Self::Word(word) => bz.extend_from_slice(word),
+            Self::Bytes(data) => {
+                bz.extend_from_slice(&word(data.len()));
+                bz.extend_from_slice(data);
+                bz.resize(bz.len() + (32 - data.len() % 32) % 32, 0);
+            }
+            Self::Array(tokens) => {
+                bz.extend_from_slice(&word(tokens.len()));
+                encode_tuple(tokens, bz);
+            }
+            Self::Tuple(tokens) => encode_tuple(tokens, bz),
+        }
+    }
+}
+
+/// Equivalent of Solidity's `abi.encode(token)`.
+pub fn encode(token: &Token) -> Vec<u8> {
+    let mut bz = Vec::new();
+    encode_tuple(std::slice::from_ref(token), &mut bz);
+    bz
+}
+
+fn encode_tuple(tokens: &[Token], bz: &mut Vec<u8>) {
+    let head_len = tokens.iter().map(Token::head_len).sum::<usize>();
+
+    let mut head = Vec::with_capacity(head_len);
+    let mut tail = Vec::new();
+    for token in tokens {
+        if token.is_dynamic() {
+            head.extend_from_slice(&word(head_len + tail.len()));
+            token.encode_into(&mut tail);
+        } else {
+            token.encode_into(&mut head);
+        }
+    }
+
+    bz.extend_from_slice(&head);
+    bz.extend_from_slice(&tail);
+}
+
+fn word(value: usize) -> [u8; 32] {
+    let mut word = [0u8; 32];
+    word[24..].copy_from_slice(&(value as u64).to_be_bytes());
+    word
+}
@@ -1,21 +1,53 @@
 use cosmwasm_std::{
-    entry_point, to_json_binary, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response,
+    entry_point, to_json_binary, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Reply, Response,
 };
-use cw2::set_contract_version;
+use cw2::{get_contract_version, set_contract_version};
 
 use crate::{
     error::{ContractError, ContractResponse, ContractResult},
-    execute::{fill, open},
+    execute::{
+        add_destination_settler, add_order_data_type, deposit, fill, open, open_for, open_reply,
+        remove_chain_domain, remove_order_data_type, retry_settlement, set_chain_domain,
+        update_gateway, update_ownership, withdraw,
+    },
+    helpers::go_fast_json_order_data_type,
     msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
-    query::resolve,
-    state::GATEWAY_ADDRESS,
+    query::{
+        get_auto_settled_fills, get_chain_domains, get_config, get_deposits,
+        get_destination_settlers, get_order_by_id, get_order_data_types, get_order_status,
+        get_orders_by_user, resolve,
+    },
+    state::{GATEWAY_ADDRESS, ORDER_DATA_TYPES},
+    types::{OrderDataDecoder, OrderDataType},
 };
 
 // version info for migration info
 const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
-pub const GO_FAST_ORDER_TYPE: &str = "GO_FAST_ORDER";
+/// Type string of the JSON encoded `OrderData` opened on CosmWasm. Orders
+/// carry its keccak256 hash as their `order_data_type`, see
+/// `helpers::go_fast_json_order_data_type`.
+///
+/// This is not the ABI struct of the Solidity `GoFastERC7683`, whose
+/// `GO_FAST_JSON_ORDER_DATA_TYPE` hashes to a different `order_data_type`. Order
+/// data of this type is decoded as JSON by `OrderDataDecoder::GoFast`, so the
+/// field names and optional fields follow the JSON layout.
+pub const GO_FAST_JSON_ORDER_DATA_TYPE: &str = concat!(
+    "GoFastJsonOrderData(",
+    "bytes32 sender,",
+    "bytes32 recipient,",
+    "string input_token,",
+    "string output_token,",
+    "uint128 amount_in,",
+    "uint128 amount_out,",
+    "uint32 destination_domain,",
+    "uint64 destination_chain_id,",
+    "uint64 timeout_timestamp,",
+    "bytes data)"
+);
+
+pub const OPEN_REPLY_ID: u64 = 1;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(_deps: DepsMut, _env: Env, _msg: Empty) -> Result<Response, ContractError> {
@@ -35,25 +67,90 @@ pub fn instantiate(
 
     GATEWAY_ADDRESS.save(deps.storage, &msg.gateway_address)?;
 
+    let order_data_type = go_fast_json_order_data_type();
+    ORDER_DATA_TYPES.save(
+        deps.storage,
+        order_data_type.to_vec(),
+        &OrderDataType {
+            order_data_type,
+            gateway: msg.gateway_address,
+            decoder: OrderDataDecoder::GoFast,
+        },
+    )?;
+
     Ok(Response::default())
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn execute(deps: DepsMut, _env: Env, info: MessageInfo, msg: ExecuteMsg) -> ContractResponse {
+pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> ContractResponse {
     match msg {
         ExecuteMsg::Open { order } => open(deps, info, order),
+        ExecuteMsg::OpenFor {
+            order,
+            signature,
+            origin_filler_data,
+        } => open_for(deps, env, order, signature, origin_filler_data),
+        ExecuteMsg::Deposit { recipient } => deposit(deps, info, recipient),
+        ExecuteMsg::Withdraw { amount } => withdraw(deps, info, amount),
         ExecuteMsg::Fill {
             order_id,
             origin_data,
-            filler_data: _,
-        } => fill(deps, info, order_id, origin_data),
+            filler_data,
+        } => fill(deps, env, info, order_id, origin_data, filler_data),
+        ExecuteMsg::RetrySettlement { order_id } => retry_settlement(deps, info, order_id),
+        ExecuteMsg::UpdateOwnership(action) => update_ownership(deps, env, info, action),
+        ExecuteMsg::UpdateGateway { gateway_address } => {
+            update_gateway(deps, info, gateway_address)
+        }
+        ExecuteMsg::AddDestinationSettler { domain, settler } => {
+            add_destination_settler(deps, info, domain, settler)
+        }
+        ExecuteMsg::SetChainDomain { chain_id, domain } => {
+            set_chain_domain(deps, info, chain_id, domain)
+        }
+        ExecuteMsg::RemoveChainDomain { chain_id } => remove_chain_domain(deps, info, chain_id),
+        ExecuteMsg::AddOrderDataType {
+            order_data_type,
+            gateway_address,
+            decoder,
+        } => add_order_data_type(deps, info, order_data_type, gateway_address, decoder),
+        ExecuteMsg::RemoveOrderDataType { order_data_type } => {
+            remove_order_data_type(deps, info, order_data_type)
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> ContractResponse {
+    match msg.id {
+        OPEN_REPLY_ID => open_reply(deps, msg),
+        id => Err(ContractError::UnknownReplyId(id)),
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(_deps: Deps, _env: Env, msg: QueryMsg) -> ContractResult<Binary> {
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> ContractResult<Binary> {
     match msg {
-        QueryMsg::Resolve { order } => to_json_binary(&resolve(order)?),
+        QueryMsg::Config {} => to_json_binary(&get_config(deps)?),
+        QueryMsg::ContractVersion {} => to_json_binary(&get_contract_version(deps.storage)?),
+        QueryMsg::Ownership {} => to_json_binary(&cw_ownable::get_ownership(deps.storage)?),
+        QueryMsg::Resolve { order } => to_json_binary(&resolve(deps, order)?),
+        QueryMsg::DestinationSettlers {} => to_json_binary(&get_destination_settlers(deps)?),
+        QueryMsg::OrderDataTypes {} => to_json_binary(&get_order_data_types(deps)?),
+        QueryMsg::ChainDomains {} => to_json_binary(&get_chain_domains(deps)?),
+        QueryMsg::Deposits { user } => to_json_binary(&get_deposits(deps, user)?),
+        QueryMsg::OrderById { order_id } => to_json_binary(&get_order_by_id(deps, order_id)?),
+        QueryMsg::OrdersByUser {
+            user,
+            start_after,
+            limit,
+        } => to_json_binary(&get_orders_by_user(deps, user, start_after, limit)?),
+        QueryMsg::OrderStatus { order_id } => to_json_binary(&get_order_status(deps, order_id)?),
+        QueryMsg::AutoSettledFills {
+            filler,
+            start_after,
+            limit,
+        } => to_json_binary(&get_auto_settled_fills(deps, filler, start_after, limit)?),
     }
     .map_err(From::from)
 }
@@ -1,12 +1,95 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{Coin, StdError};
+use cw_ownable::OwnershipError;
+use cw_utils::ParseReplyError;
 
 #[derive(Debug, thiserror::Error)]
 pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error(transparent)]
+    Ownership(#[from] OwnershipError),
+
     #[error("Wrong order data type")]
     WrongOrderDataType,
+
+    #[error("Unknown destination domain")]
+    UnknownDestinationDomain,
+
+    #[error("Unknown chain id: {0}")]
+    UnknownChainId(u64),
+
+    #[error("Unknown domain: {0}")]
+    UnknownDomain(u32),
+
+    #[error("Order data has no destination domain or chain id")]
+    MissingDestination,
+
+    #[error("Destination domain does not match destination chain id")]
+    DestinationMismatch,
+
+    #[error("Origin chain id does not match the local domain")]
+    InvalidOriginChainId,
+
+    #[error("Invalid signature")]
+    InvalidSignature,
+
+    #[error("Invalid origin settler")]
+    InvalidOriginSettler,
+
+    #[error("Open deadline passed")]
+    OpenDeadlinePassed,
+
+    #[error("Origin filler data is not supported")]
+    UnsupportedOriginFillerData,
+
+    #[error("Nonce already used")]
+    NonceAlreadyUsed,
+
+    #[error("Order sender does not match user")]
+    InvalidOrderSender,
+
+    #[error("No funds sent")]
+    NoFunds,
+
+    #[error("Insufficient deposit")]
+    InsufficientDeposit,
+
+    #[error(transparent)]
+    ParseReply(#[from] ParseReplyError),
+
+    #[error("Gateway did not return an order id")]
+    MissingOrderId,
+
+    #[error("Gateway returned order id {actual}, expected {expected}")]
+    UnexpectedOrderId { expected: String, actual: String },
+
+    #[error("Invalid origin data")]
+    InvalidOriginData,
+
+    #[error("Order ID does not match origin data")]
+    OrderIdMismatch,
+
+    #[error("Invalid {0}: expected 32 bytes")]
+    InvalidBytes32(String),
+
+    #[error("Invalid repayment address")]
+    InvalidRepaymentAddress,
+
+    #[error("Repayment address can only be set when auto settling")]
+    RepaymentAddressWithoutAutoSettle,
+
+    #[error("No auto settled fill of order {0} by the sender")]
+    AutoSettledFillNotFound(String),
+
+    #[error("Unexpected funds sent. Expected: {expected:?}, Actual: {actual:?}")]
+    UnexpectedFunds {
+        expected: Vec<Coin>,
+        actual: Vec<Coin>,
+    },
+
+    #[error("Unknown reply id: {0}")]
+    UnknownReplyId(u64),
 }
 
 pub type ContractResult<T> = Result<T, ContractError>;
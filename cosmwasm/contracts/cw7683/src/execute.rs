@@ -1,71 +1,544 @@
 use crate::{
-    error::ContractResponse,
-    query::resolve,
-    state::GATEWAY_ADDRESS,
-    types::{OnchainCrossChainOrder, OrderData},
+    contract::OPEN_REPLY_ID,
+    error::{ContractError, ContractResponse, ContractResult},
+    helpers::{assert_bytes32, verify_order_signature},
+    query::resolve_order,
+    state::{
+        self, AUTO_SETTLED_FILLS, CHAIN_DOMAINS, DEPOSITS, DESTINATION_SETTLERS, DOMAIN_CHAIN_IDS,
+        GATEWAY_ADDRESS, OPENING_ORDER_ID, ORDER_DATA_TYPES, USED_NONCES,
+    },
+    types::{
+        AutoSettledFill, FillerData, GaslessCrossChainOrder, OnchainCrossChainOrder, OrderData,
+        OrderDataDecoder, OrderDataType, OrderSignature, ResolvedCrossChainOrder,
+    },
 };
 use cosmwasm_std::{
-    from_json, to_json_binary, Binary, DepsMut, HexBinary, MessageInfo, Response, WasmMsg,
+    coin, from_json, to_json_binary, Addr, BankMsg, Binary, Coin, DepsMut, Empty, Env, Event,
+    HexBinary, MessageInfo, Order, Reply, Response, StdResult, Storage, SubMsg, Uint128, WasmMsg,
 };
+use cw_ownable::assert_owner;
+use cw_utils::parse_reply_execute_data;
 use go_fast::{
-    gateway::ExecuteMsg as GatewayExecuteMsg, helpers::keccak256_hash, FastTransferOrder,
+    gateway::{Config, ExecuteMsg as GatewayExecuteMsg, QueryMsg as GatewayQueryMsg},
+    helpers::{bech32_decode, keccak256_hash, left_pad_bytes},
+    FastTransferOrder,
 };
 
 pub fn open(deps: DepsMut, info: MessageInfo, order: OnchainCrossChainOrder) -> ContractResponse {
-    let gateway_address = GATEWAY_ADDRESS.load(deps.storage)?;
+    let (resolved_order, order_data, order_data_type) = resolve_order(deps.as_ref(), &order)?;
 
-    let order_data: OrderData = from_json(&order.order_data)?;
+    // The gateway trusts the settler to submit orders on behalf of others.
+    let sender_hex = HexBinary::from(left_pad_bytes(bech32_decode(info.sender.as_str())?, 32));
+    if order_data.sender != sender_hex {
+        return Err(ContractError::InvalidOrderSender);
+    }
 
-    let resolved_order = resolve(order.clone())?;
+    let msg = submit_order_msg(
+        &order_data_type.gateway,
+        order_data,
+        order.fill_deadline,
+        info.funds,
+    )?;
 
-    let order_id = keccak256_hash(&resolved_order.fill_instructions[0].origin_data);
+    state::opened_orders().create_opened_order(
+        deps.storage,
+        info.sender,
+        order_data_type.gateway,
+        resolved_order.clone(),
+    )?;
+    OPENING_ORDER_ID.save(deps.storage, &resolved_order.order_id)?;
 
+    Ok(Response::new()
+        .add_submessage(msg)
+        .add_event(open_event(&resolved_order)?)
+        .add_attribute("action", "open"))
+}
+
+pub fn open_for(
+    deps: DepsMut,
+    env: Env,
+    order: GaslessCrossChainOrder,
+    signature: OrderSignature,
+    origin_filler_data: Binary,
+) -> ContractResponse {
+    if order.origin_settler != env.contract.address {
+        return Err(ContractError::InvalidOriginSettler);
+    }
+
+    if !origin_filler_data.is_empty() {
+        return Err(ContractError::UnsupportedOriginFillerData);
+    }
+
+    if env.block.time.seconds() > u64::from(order.open_deadline) {
+        return Err(ContractError::OpenDeadlinePassed);
+    }
+
+    let origin_chain_id = order.origin_chain_id;
+
+    verify_order_signature(deps.as_ref(), &order, &signature)?;
+
+    let user = deps.api.addr_validate(&order.user)?;
+    if USED_NONCES.has(deps.storage, (user.clone(), order.nonce)) {
+        return Err(ContractError::NonceAlreadyUsed);
+    }
+    USED_NONCES.save(deps.storage, (user.clone(), order.nonce), &Empty {})?;
+
+    let (mut resolved_order, order_data, order_data_type) = resolve_order(
+        deps.as_ref(),
+        &OnchainCrossChainOrder {
+            fill_deadline: order.fill_deadline,
+            order_data_type: order.order_data_type,
+            order_data: order.order_data,
+        },
+    )?;
+    resolved_order.open_deadline = order.open_deadline;
+
+    if resolved_order.origin_chain_id != origin_chain_id {
+        return Err(ContractError::InvalidOriginChainId);
+    }
+
+    let user_hex = HexBinary::from(left_pad_bytes(bech32_decode(user.as_str())?, 32));
+    if order_data.sender != user_hex {
+        return Err(ContractError::InvalidOrderSender);
+    }
+
+    let funds = coin(order_data.amount_in.u128(), order_data.input_token.clone());
+    withdraw_deposit(deps.storage, &user, &funds)?;
+
+    let msg = submit_order_msg(
+        &order_data_type.gateway,
+        order_data,
+        order.fill_deadline,
+        vec![funds],
+    )?;
+
+    state::opened_orders().create_opened_order(
+        deps.storage,
+        user.clone(),
+        order_data_type.gateway,
+        resolved_order.clone(),
+    )?;
+    OPENING_ORDER_ID.save(deps.storage, &resolved_order.order_id)?;
+
+    Ok(Response::new()
+        .add_submessage(msg)
+        .add_event(open_event(&resolved_order)?)
+        .add_attribute("action", "open_for")
+        .add_attribute("user", user))
+}
+
+pub fn deposit(deps: DepsMut, info: MessageInfo, recipient: Option<String>) -> ContractResponse {
+    if info.funds.is_empty() {
+        return Err(ContractError::NoFunds);
+    }
+
+    let user = match recipient {
+        Some(recipient) => deps.api.addr_validate(&recipient)?,
+        None => info.sender.clone(),
+    };
+
+    for coin in &info.funds {
+        DEPOSITS.update(
+            deps.storage,
+            (user.clone(), coin.denom.clone()),
+            |balance| -> StdResult<_> { Ok(balance.unwrap_or_default() + coin.amount) },
+        )?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "deposit")
+        .add_attribute("sender", info.sender)
+        .add_attribute("user", user))
+}
+
+pub fn withdraw(deps: DepsMut, info: MessageInfo, amount: Coin) -> ContractResponse {
+    withdraw_deposit(deps.storage, &info.sender, &amount)?;
+
+    Ok(Response::new()
+        .add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: vec![amount],
+        })
+        .add_attribute("action", "withdraw")
+        .add_attribute("user", info.sender))
+}
+
+fn withdraw_deposit(storage: &mut dyn Storage, user: &Addr, amount: &Coin) -> ContractResult<()> {
+    let key = (user.clone(), amount.denom.clone());
+    let balance = DEPOSITS.may_load(storage, key.clone())?.unwrap_or_default();
+    if balance < amount.amount {
+        return Err(ContractError::InsufficientDeposit);
+    }
+
+    DEPOSITS.save(storage, key, &(balance - amount.amount))?;
+
+    Ok(())
+}
+
+fn submit_order_msg(
+    gateway_address: &Addr,
+    order_data: OrderData,
+    fill_deadline: u32,
+    funds: Vec<Coin>,
+) -> ContractResult<SubMsg> {
     let msg = GatewayExecuteMsg::SubmitOrder {
         sender: order_data.sender,
         recipient: order_data.recipient,
         amount_in: order_data.amount_in,
         amount_out: order_data.amount_out,
-        destination_domain: order_data.destination_domain,
-        timeout_timestamp: order.fill_deadline,
+        destination_domain: order_data
+            .destination_domain
+            .ok_or(ContractError::MissingDestination)?,
+        timeout_timestamp: fill_deadline.into(),
         data: order_data.data,
+        refund_address: None,
     };
 
     let msg = WasmMsg::Execute {
         contract_addr: gateway_address.to_string(),
         msg: to_json_binary(&msg)?,
-        funds: info.funds,
+        funds,
     };
 
+    Ok(SubMsg::reply_on_success(msg, OPEN_REPLY_ID))
+}
+
+/// Mirrors the ERC-7683 `Open(bytes32 indexed orderId, ResolvedCrossChainOrder
+/// resolvedOrder)` event, with the resolved order ABI encoded.
+fn open_event(resolved_order: &ResolvedCrossChainOrder) -> StdResult<Event> {
+    Ok(Event::new("open")
+        .add_attribute("order_id", resolved_order.order_id.to_string())
+        .add_attribute("resolved_order", resolved_order.abi_encode()?.to_string()))
+}
+
+/// Emits the order ID the gateway assigned to the order submitted by `open`,
+/// which has to be the ID the order was resolved and recorded with.
+pub fn open_reply(deps: DepsMut, reply: Reply) -> ContractResponse {
+    let order_id = parse_reply_execute_data(reply)?
+        .data
+        .map(HexBinary::from)
+        .ok_or(ContractError::MissingOrderId)?;
+
+    let expected_order_id = OPENING_ORDER_ID.load(deps.storage)?;
+    if order_id != expected_order_id {
+        return Err(ContractError::UnexpectedOrderId {
+            expected: expected_order_id.to_string(),
+            actual: order_id.to_string(),
+        });
+    }
+
+    OPENING_ORDER_ID.remove(deps.storage);
+
+    Ok(Response::new()
+        .set_data(order_id.clone())
+        .add_attribute("order_id", order_id.to_string()))
+}
+
+pub fn update_ownership(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    action: cw_ownable::Action,
+) -> ContractResponse {
+    let ownership = cw_ownable::update_ownership(deps, &env.block, &info.sender, action)?;
+
+    Ok(Response::new().add_attributes(ownership.into_attributes()))
+}
+
+/// Points the settler at a new default gateway. Order data types routed to
+/// the previous gateway are moved over with it.
+pub fn update_gateway(
+    deps: DepsMut,
+    info: MessageInfo,
+    gateway_address: String,
+) -> ContractResponse {
+    assert_owner(deps.storage, &info.sender)?;
+
+    let gateway = deps.api.addr_validate(&gateway_address)?;
+    let previous_gateway = GATEWAY_ADDRESS.load(deps.storage)?;
+
+    GATEWAY_ADDRESS.save(deps.storage, &gateway)?;
+
+    let order_data_types = ORDER_DATA_TYPES
+        .range(deps.storage, None, None, Order::Ascending)
+        .filter(|entry| matches!(entry, Ok((_, t)) if t.gateway == previous_gateway))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    for (key, mut order_data_type) in order_data_types {
+        order_data_type.gateway = gateway.clone();
+        ORDER_DATA_TYPES.save(deps.storage, key, &order_data_type)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "update_gateway")
+        .add_attribute("previous_gateway", previous_gateway)
+        .add_attribute("gateway", gateway))
+}
+
+pub fn add_destination_settler(
+    deps: DepsMut,
+    info: MessageInfo,
+    domain: u32,
+    settler: HexBinary,
+) -> ContractResponse {
+    assert_owner(deps.storage, &info.sender)?;
+
+    assert_bytes32(&settler, "destination settler")?;
+
+    DESTINATION_SETTLERS.save(deps.storage, domain, &settler)?;
+
+    Ok(Response::default())
+}
+
+pub fn set_chain_domain(
+    deps: DepsMut,
+    info: MessageInfo,
+    chain_id: u64,
+    domain: u32,
+) -> ContractResponse {
+    assert_owner(deps.storage, &info.sender)?;
+
+    // drop any mapping either side had, keeping the registry one to one
+    if let Some(previous_domain) = CHAIN_DOMAINS.may_load(deps.storage, chain_id)? {
+        DOMAIN_CHAIN_IDS.remove(deps.storage, previous_domain);
+    }
+    if let Some(previous_chain_id) = DOMAIN_CHAIN_IDS.may_load(deps.storage, domain)? {
+        CHAIN_DOMAINS.remove(deps.storage, previous_chain_id);
+    }
+
+    CHAIN_DOMAINS.save(deps.storage, chain_id, &domain)?;
+    DOMAIN_CHAIN_IDS.save(deps.storage, domain, &chain_id)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_chain_domain")
+        .add_attribute("chain_id", chain_id.to_string())
+        .add_attribute("domain", domain.to_string()))
+}
+
+pub fn remove_chain_domain(deps: DepsMut, info: MessageInfo, chain_id: u64) -> ContractResponse {
+    assert_owner(deps.storage, &info.sender)?;
+
+    if let Some(domain) = CHAIN_DOMAINS.may_load(deps.storage, chain_id)? {
+        CHAIN_DOMAINS.remove(deps.storage, chain_id);
+        DOMAIN_CHAIN_IDS.remove(deps.storage, domain);
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "remove_chain_domain")
+        .add_attribute("chain_id", chain_id.to_string()))
+}
+
+pub fn add_order_data_type(
+    deps: DepsMut,
+    info: MessageInfo,
+    order_data_type: HexBinary,
+    gateway_address: String,
+    decoder: OrderDataDecoder,
+) -> ContractResponse {
+    assert_owner(deps.storage, &info.sender)?;
+
+    let gateway = deps.api.addr_validate(&gateway_address)?;
+
+    ORDER_DATA_TYPES.save(
+        deps.storage,
+        order_data_type.to_vec(),
+        &OrderDataType {
+            order_data_type: order_data_type.clone(),
+            gateway: gateway.clone(),
+            decoder,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "add_order_data_type")
+        .add_attribute("order_data_type", order_data_type.to_string())
+        .add_attribute("gateway", gateway))
+}
+
+pub fn remove_order_data_type(
+    deps: DepsMut,
+    info: MessageInfo,
+    order_data_type: HexBinary,
+) -> ContractResponse {
+    assert_owner(deps.storage, &info.sender)?;
+
+    ORDER_DATA_TYPES.remove(deps.storage, order_data_type.to_vec());
+
     Ok(Response::new()
-        .add_message(msg)
-        .add_attribute("action", "open")
-        .add_attribute("order_id", order_id.to_string())
-        .add_attribute("order", to_json_binary(&resolved_order)?.to_string()))
+        .add_attribute("action", "remove_order_data_type")
+        .add_attribute("order_data_type", order_data_type.to_string()))
 }
 
 pub fn fill(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     order_id: HexBinary,
     origin_data: Binary,
+    filler_data: Binary,
 ) -> ContractResponse {
-    let gateway_address = GATEWAY_ADDRESS.load(deps.storage)?;
+    // the fixed size fields of the encoded order take 148 bytes
+    if origin_data.len() < 148 {
+        return Err(ContractError::InvalidOriginData);
+    }
+
+    if keccak256_hash(&origin_data) != order_id {
+        return Err(ContractError::OrderIdMismatch);
+    }
 
     let order: FastTransferOrder = HexBinary::from(origin_data).into();
 
-    let msg = GatewayExecuteMsg::FillOrder {
-        filler: info.sender.clone(),
-        order,
+    let filler_data: FillerData = if filler_data.is_empty() {
+        FillerData::default()
+    } else {
+        from_json(&filler_data)?
     };
 
-    let msg = WasmMsg::Execute {
-        contract_addr: gateway_address.to_string(),
-        msg: to_json_binary(&msg)?,
-        funds: info.funds,
+    let gateway_address = match filler_data.order_data_type {
+        Some(order_data_type) => {
+            ORDER_DATA_TYPES
+                .may_load(deps.storage, order_data_type.to_vec())?
+                .ok_or(ContractError::WrongOrderDataType)?
+                .gateway
+        }
+        None => GATEWAY_ADDRESS.load(deps.storage)?,
     };
+    let config: Config = deps
+        .querier
+        .query_wasm_smart(&gateway_address, &GatewayQueryMsg::Config {})?;
 
-    Ok(Response::new()
-        .add_message(msg)
+    let (fill_funds, settlement_funds) =
+        split_fill_funds(&info.funds, &config.token_denom, order.amount_out)?;
+
+    if !filler_data.auto_settle && filler_data.repayment_address.is_some() {
+        return Err(ContractError::RepaymentAddressWithoutAutoSettle);
+    }
+
+    if !filler_data.auto_settle && !settlement_funds.is_empty() {
+        return Err(ContractError::UnexpectedFunds {
+            expected: vec![fill_funds],
+            actual: info.funds,
+        });
+    }
+
+    // the gateway only lets the filler settle, so auto settled orders are
+    // filled by the settler itself and the solver is recorded here instead
+    let filler = if filler_data.auto_settle {
+        env.contract.address
+    } else {
+        info.sender.clone()
+    };
+
+    let mut response = Response::new()
+        .add_message(WasmMsg::Execute {
+            contract_addr: gateway_address.to_string(),
+            msg: to_json_binary(&GatewayExecuteMsg::FillOrder { filler, order })?,
+            funds: vec![fill_funds],
+        })
         .add_attribute("action", "fill")
+        .add_attribute("order_id", order_id.to_string());
+
+    if filler_data.auto_settle {
+        let repayment_address = match filler_data.repayment_address {
+            Some(repayment_address) => repayment_address,
+            None => left_pad_bytes(bech32_decode(info.sender.as_str())?, 32).into(),
+        };
+
+        if repayment_address.len() != 32 {
+            return Err(ContractError::InvalidRepaymentAddress);
+        }
+
+        AUTO_SETTLED_FILLS.save(
+            deps.storage,
+            (info.sender.clone(), order_id.to_vec()),
+            &AutoSettledFill {
+                order_id: order_id.clone(),
+                filler: info.sender,
+                gateway: gateway_address.clone(),
+                repayment_address: repayment_address.clone(),
+            },
+        )?;
+
+        response = response
+            .add_message(initiate_settlement_msg(
+                &gateway_address,
+                order_id,
+                repayment_address.clone(),
+                settlement_funds,
+            )?)
+            .add_attribute("repayment_address", repayment_address.to_string());
+    }
+
+    Ok(response)
+}
+
+/// Settles an auto settled fill of the sender again, to the repayment address
+/// it was filled with.
+pub fn retry_settlement(deps: DepsMut, info: MessageInfo, order_id: HexBinary) -> ContractResponse {
+    let fill = AUTO_SETTLED_FILLS
+        .may_load(deps.storage, (info.sender, order_id.to_vec()))?
+        .ok_or_else(|| ContractError::AutoSettledFillNotFound(order_id.to_string()))?;
+
+    Ok(Response::new()
+        .add_message(initiate_settlement_msg(
+            &fill.gateway,
+            fill.order_id,
+            fill.repayment_address,
+            info.funds,
+        )?)
+        .add_attribute("action", "retry_settlement")
         .add_attribute("order_id", order_id.to_string()))
 }
+
+fn initiate_settlement_msg(
+    gateway_address: &Addr,
+    order_id: HexBinary,
+    repayment_address: HexBinary,
+    funds: Vec<Coin>,
+) -> StdResult<WasmMsg> {
+    Ok(WasmMsg::Execute {
+        contract_addr: gateway_address.to_string(),
+        msg: to_json_binary(&GatewayExecuteMsg::InitiateSettlement {
+            order_ids: vec![order_id],
+            repayment_address,
+        })?,
+        funds,
+    })
+}
+
+/// Splits the funds sent with a fill into the order's output and whatever is
+/// left over to pay for settlement.
+fn split_fill_funds(
+    funds: &[Coin],
+    denom: &str,
+    amount: Uint128,
+) -> ContractResult<(Coin, Vec<Coin>)> {
+    let fill_funds = coin(amount.u128(), denom);
+
+    let sent = funds
+        .iter()
+        .find(|c| c.denom == denom)
+        .map(|c| c.amount)
+        .unwrap_or_default();
+    if sent < amount {
+        return Err(ContractError::UnexpectedFunds {
+            expected: vec![fill_funds],
+            actual: funds.to_vec(),
+        });
+    }
+
+    let settlement_funds = funds
+        .iter()
+        .filter_map(|c| {
+            let amount = if c.denom == denom {
+                c.amount - amount
+            } else {
+                c.amount
+            };
+
+            (!amount.is_zero()).then(|| coin(amount.u128(), &c.denom))
+        })
+        .collect();
+
+    Ok((fill_funds, settlement_funds))
+}
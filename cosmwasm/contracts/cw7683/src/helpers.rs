@@ -0,0 +1,150 @@
+use cosmwasm_std::{to_json_vec, Binary, Deps, HexBinary};
+use go_fast::helpers::{bech32_decode, keccak256_hash, left_pad_bytes};
+
+use crate::{
+    contract::GO_FAST_JSON_ORDER_DATA_TYPE,
+    error::{ContractError, ContractResult},
+    state::{CHAIN_DOMAINS, DOMAIN_CHAIN_IDS},
+    types::{GaslessCrossChainOrder, OrderData, OrderSignature},
+};
+
+/// ADR-36 sign doc of `data` signed by `signer`, the bytes wallets sign for
+/// Cosmos `signArbitrary` requests.
+pub fn adr36_sign_doc(signer: &str, data: &[u8]) -> Vec<u8> {
+    format!(
+        concat!(
+            r#"{{"account_number":"0","chain_id":"","fee":{{"amount":[],"gas":"0"}},"memo":"","#,
+            r#""msgs":[{{"type":"sign/MsgSignData","value":{{"data":"{}","signer":"{}"}}}}],"#,
+            r#""sequence":"0"}}"#
+        ),
+        Binary::from(data).to_base64(),
+        signer
+    )
+    .into_bytes()
+}
+
+/// EIP-191 hash of `message`, as signed by Ethereum wallets for
+/// `personal_sign` requests.
+pub fn eip191_hash(message: &[u8]) -> HexBinary {
+    let mut prefixed = format!("\x19Ethereum Signed Message:\n{}", message.len()).into_bytes();
+    prefixed.extend_from_slice(message);
+
+    keccak256_hash(&prefixed)
+}
+
+/// Verifies that `signature` was produced over the JSON encoding of `order` by
+/// the key controlling `order.user`, wrapped in an ADR-36 sign doc for
+/// `Secp256k1` keys and prefixed as per EIP-191 for `EthSecp256k1` keys.
+pub fn verify_order_signature(
+    deps: Deps,
+    order: &GaslessCrossChainOrder,
+    signature: &OrderSignature,
+) -> ContractResult<()> {
+    use ripemd::Ripemd160;
+    use sha2::{Digest, Sha256};
+
+    let sign_bytes = to_json_vec(order)?;
+    let user = bech32_decode(&order.user)?;
+
+    let signer = match signature {
+        OrderSignature::Secp256k1 { pub_key, signature } => {
+            let hash = Sha256::digest(adr36_sign_doc(&order.user, &sign_bytes));
+            let valid = deps
+                .api
+                .secp256k1_verify(&hash, signature, pub_key)
+                .map_err(|_| ContractError::InvalidSignature)?;
+            if !valid {
+                return Err(ContractError::InvalidSignature);
+            }
+
+            Ripemd160::digest(Sha256::digest(pub_key.as_slice())).to_vec()
+        }
+        OrderSignature::EthSecp256k1 { signature } => {
+            if signature.len() != 65 {
+                return Err(ContractError::InvalidSignature);
+            }
+
+            let hash = eip191_hash(&sign_bytes);
+            let recovery_param = match signature[64] {
+                v @ (0 | 1) => v,
+                v @ (27 | 28) => v - 27,
+                _ => return Err(ContractError::InvalidSignature),
+            };
+
+            let pub_key = deps
+                .api
+                .secp256k1_recover_pubkey(&hash, &signature[..64], recovery_param)
+                .map_err(|_| ContractError::InvalidSignature)?;
+
+            keccak256_hash(&pub_key[1..])[12..].to_vec()
+        }
+    };
+
+    if signer != user.as_slice() {
+        return Err(ContractError::InvalidSignature);
+    }
+
+    Ok(())
+}
+
+/// The ERC-7683 `orderDataType` of JSON encoded Go Fast orders.
+pub fn go_fast_json_order_data_type() -> HexBinary {
+    keccak256_hash(GO_FAST_JSON_ORDER_DATA_TYPE.as_bytes())
+}
+
+/// Errors unless `value`, named `name` in the error, is exactly 32 bytes long.
+pub fn assert_bytes32(value: &HexBinary, name: &str) -> ContractResult<()> {
+    if value.len() != 32 {
+        return Err(ContractError::InvalidBytes32(name.to_string()));
+    }
+
+    Ok(())
+}
+
+/// Converts a token into the `bytes32` used by ERC-7683 outputs. Hex encoded
+/// EVM tokens are decoded and left padded, denoms of up to 32 bytes are left
+/// padded as is, and longer denoms (e.g. `ibc/...`) are keccak256 hashed.
+pub fn token_to_bytes32(token: &str) -> HexBinary {
+    if let Some(bz) = token
+        .strip_prefix("0x")
+        .and_then(|hex| HexBinary::from_hex(hex).ok())
+        .filter(|bz| bz.len() <= 32)
+    {
+        return left_pad_bytes(bz.to_vec(), 32).into();
+    }
+
+    if token.len() <= 32 {
+        return left_pad_bytes(token.as_bytes().to_vec(), 32).into();
+    }
+
+    keccak256_hash(token.as_bytes())
+}
+
+/// The chain ID registered for `domain`. Domains have to be registered, not
+/// every Hyperlane domain matches the chain ID of its chain.
+pub fn domain_to_chain_id(deps: Deps, domain: u32) -> ContractResult<u64> {
+    DOMAIN_CHAIN_IDS
+        .may_load(deps.storage, domain)?
+        .ok_or(ContractError::UnknownDomain(domain))
+}
+
+/// The Hyperlane domain `order_data` is sent to, from its destination domain
+/// or its destination chain ID. When both are given they must agree.
+pub fn order_destination_domain(deps: Deps, order_data: &OrderData) -> ContractResult<u32> {
+    let chain_domain = order_data
+        .destination_chain_id
+        .map(|chain_id| {
+            CHAIN_DOMAINS
+                .may_load(deps.storage, chain_id)?
+                .ok_or(ContractError::UnknownChainId(chain_id))
+        })
+        .transpose()?;
+
+    match (order_data.destination_domain, chain_domain) {
+        (Some(domain), Some(chain_domain)) if domain != chain_domain => {
+            Err(ContractError::DestinationMismatch)
+        }
+        (Some(domain), _) | (None, Some(domain)) => Ok(domain),
+        (None, None) => Err(ContractError::MissingDestination),
+    }
+}
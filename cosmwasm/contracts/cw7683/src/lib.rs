@@ -1,7 +1,10 @@
+pub mod abi;
 pub mod contract;
 pub mod error;
 pub mod execute;
+pub mod helpers;
 pub mod msg;
+pub mod orders;
 pub mod query;
 pub mod state;
 pub mod types;
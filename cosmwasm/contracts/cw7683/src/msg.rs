@@ -1,28 +1,117 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Binary, HexBinary};
+use cosmwasm_std::{Addr, Binary, Coin, HexBinary};
 
-use crate::types::{OnchainCrossChainOrder, ResolvedCrossChainOrder};
+use cw2::ContractVersion;
+use cw_ownable::{cw_ownable_execute, cw_ownable_query};
+use go_fast::gateway::OrderStatusResponse;
+
+use crate::types::{
+    AutoSettledFill, ChainDomain, Config, DestinationSettler, GaslessCrossChainOrder,
+    OnchainCrossChainOrder, OpenedOrder, OrderDataDecoder, OrderDataType, OrderSignature,
+    ResolvedCrossChainOrder,
+};
 
 #[cw_serde]
 pub struct InstantiateMsg {
     pub gateway_address: Addr,
 }
 
+#[cw_ownable_execute]
 #[cw_serde]
 pub enum ExecuteMsg {
     Open {
         order: OnchainCrossChainOrder,
     },
+    OpenFor {
+        order: GaslessCrossChainOrder,
+        signature: OrderSignature,
+        /// No origin filler data is supported yet, so it must be empty.
+        origin_filler_data: Binary,
+    },
+    /// Credits the funds sent to the deposit of `recipient`, defaulting to the
+    /// sender, for gasless orders opened on their behalf.
+    Deposit {
+        recipient: Option<String>,
+    },
+    Withdraw {
+        amount: Coin,
+    },
     Fill {
         order_id: HexBinary,
         origin_data: Binary,
         filler_data: Binary,
     },
+    /// Initiates settlement of an auto settled fill again, after the first
+    /// settlement failed to deliver. Funds pay for the settlement message.
+    RetrySettlement {
+        order_id: HexBinary,
+    },
+    UpdateGateway {
+        gateway_address: String,
+    },
+    AddDestinationSettler {
+        domain: u32,
+        settler: HexBinary,
+    },
+    SetChainDomain {
+        chain_id: u64,
+        domain: u32,
+    },
+    RemoveChainDomain {
+        chain_id: u64,
+    },
+    AddOrderDataType {
+        order_data_type: HexBinary,
+        gateway_address: String,
+        decoder: OrderDataDecoder,
+    },
+    RemoveOrderDataType {
+        order_data_type: HexBinary,
+    },
 }
 
+#[cw_ownable_query]
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
+    #[returns(Config)]
+    Config {},
+
+    #[returns(ContractVersion)]
+    ContractVersion {},
+
     #[returns(ResolvedCrossChainOrder)]
     Resolve { order: OnchainCrossChainOrder },
+
+    #[returns(Vec<DestinationSettler>)]
+    DestinationSettlers {},
+
+    #[returns(Vec<OrderDataType>)]
+    OrderDataTypes {},
+
+    #[returns(Vec<ChainDomain>)]
+    ChainDomains {},
+
+    #[returns(Vec<Coin>)]
+    Deposits { user: String },
+
+    #[returns(OpenedOrder)]
+    OrderById { order_id: HexBinary },
+
+    #[returns(Vec<OpenedOrder>)]
+    OrdersByUser {
+        user: String,
+        start_after: Option<HexBinary>,
+        limit: Option<u32>,
+    },
+
+    #[returns(OrderStatusResponse)]
+    OrderStatus { order_id: HexBinary },
+
+    #[returns(Vec<AutoSettledFill>)]
+    AutoSettledFills {
+        filler: String,
+        start_after: Option<HexBinary>,
+        limit: Option<u32>,
+    },
 }
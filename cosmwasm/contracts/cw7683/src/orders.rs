@@ -0,0 +1,89 @@
+use cosmwasm_std::{Addr, Deps, HexBinary, Order as ListOrder, StdResult, Storage};
+use cw_storage_plus::{Bound, Index, IndexList, IndexedMap, MultiIndex};
+
+use crate::types::{OpenedOrder, ResolvedCrossChainOrder};
+
+pub struct Orders<'a> {
+    orders: IndexedMap<'a, Vec<u8>, OpenedOrder, OrderIndexes<'a>>,
+}
+
+pub struct OrderIndexes<'a> {
+    pub user: MultiIndex<'a, Addr, OpenedOrder, Vec<u8>>,
+}
+
+#[allow(clippy::needless_lifetimes)]
+impl<'a> IndexList<OpenedOrder> for OrderIndexes<'a> {
+    fn get_indexes(
+        &'_ self,
+    ) -> Box<dyn Iterator<Item = &'_ dyn cw_storage_plus::Index<OpenedOrder>> + '_> {
+        let v: Vec<&dyn Index<OpenedOrder>> = vec![&self.user];
+        Box::new(v.into_iter())
+    }
+}
+
+impl<'a> Orders<'a> {
+    pub fn new(orders_namespace: &'a str, user_index_namespace: &'a str) -> Self {
+        let indexes = OrderIndexes {
+            user: MultiIndex::new(
+                |_pk, d| d.user.clone(),
+                orders_namespace,
+                user_index_namespace,
+            ),
+        };
+
+        Self {
+            orders: IndexedMap::new(orders_namespace, indexes),
+        }
+    }
+
+    pub fn create_opened_order(
+        &self,
+        storage: &mut dyn Storage,
+        user: Addr,
+        gateway: Addr,
+        resolved_order: ResolvedCrossChainOrder,
+    ) -> StdResult<OpenedOrder> {
+        let order = OpenedOrder {
+            order_id: resolved_order.order_id.clone(),
+            user,
+            gateway,
+            resolved_order,
+        };
+
+        self.orders.save(storage, order.order_id.to_vec(), &order)?;
+
+        Ok(order)
+    }
+
+    pub fn by_order_id(&self, deps: Deps, order_id: HexBinary) -> StdResult<OpenedOrder> {
+        self.orders.load(deps.storage, order_id.to_vec())
+    }
+
+    pub fn may_load_by_order_id(
+        &self,
+        deps: Deps,
+        order_id: HexBinary,
+    ) -> StdResult<Option<OpenedOrder>> {
+        self.orders.may_load(deps.storage, order_id.to_vec())
+    }
+
+    pub fn by_user(
+        &self,
+        deps: Deps,
+        user: Addr,
+        start_after: Option<Vec<u8>>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<OpenedOrder>> {
+        let limit = limit.unwrap_or(10) as usize;
+        let start: Option<Bound<Vec<u8>>> = start_after.map(Bound::exclusive);
+
+        self.orders
+            .idx
+            .user
+            .prefix(user)
+            .range(deps.storage, start, None, ListOrder::Ascending)
+            .take(limit)
+            .map(|x| x.map(|(_, order)| order))
+            .collect()
+    }
+}
@@ -1,58 +1,206 @@
-use cosmwasm_std::{from_json, to_json_binary};
-use go_fast::FastTransferOrder;
+use cosmwasm_std::{Coin, Deps, HexBinary, Order, StdResult};
+use cw_storage_plus::Bound;
+use go_fast::{
+    gateway::{OrderStatusResponse, QueryMsg as GatewayQueryMsg},
+    FastTransferOrder,
+};
 
 use crate::{
-    contract::GO_FAST_ORDER_TYPE,
     error::{ContractError, ContractResult},
-    types::{FillInstruction, OnchainCrossChainOrder, OrderData, Output, ResolvedCrossChainOrder},
+    helpers::{assert_bytes32, domain_to_chain_id, order_destination_domain, token_to_bytes32},
+    state::{
+        self, AUTO_SETTLED_FILLS, CHAIN_DOMAINS, DEPOSITS, DESTINATION_SETTLERS, GATEWAY_ADDRESS,
+        ORDER_DATA_TYPES,
+    },
+    types::{
+        AutoSettledFill, ChainDomain, Config, DestinationSettler, FillInstruction,
+        OnchainCrossChainOrder, OpenedOrder, OrderData, OrderDataType, Output,
+        ResolvedCrossChainOrder,
+    },
 };
 
-pub fn resolve(order: OnchainCrossChainOrder) -> ContractResult<ResolvedCrossChainOrder> {
-    if order.order_data_type != GO_FAST_ORDER_TYPE {
-        return Err(ContractError::WrongOrderDataType);
-    }
+/// Resolves `order` into the order the gateway will create when it is opened,
+/// using the gateway's next nonce and local domain.
+pub fn resolve(
+    deps: Deps,
+    order: OnchainCrossChainOrder,
+) -> ContractResult<ResolvedCrossChainOrder> {
+    let (resolved_order, _, _) = resolve_order(deps, &order)?;
+
+    Ok(resolved_order)
+}
+
+/// Resolves `order` with the gateway and decoder registered for its
+/// `order_data_type`, also returning the type and the decoded order data with
+/// both its destination domain and chain ID filled in.
+pub fn resolve_order(
+    deps: Deps,
+    order: &OnchainCrossChainOrder,
+) -> ContractResult<(ResolvedCrossChainOrder, OrderData, OrderDataType)> {
+    let order_data_type = ORDER_DATA_TYPES
+        .may_load(deps.storage, order.order_data_type.to_vec())?
+        .ok_or(ContractError::WrongOrderDataType)?;
+
+    let mut order_data = order_data_type.decoder.decode(&order.order_data)?;
+    assert_bytes32(&order_data.sender, "order sender")?;
+    assert_bytes32(&order_data.recipient, "order recipient")?;
 
-    let order_data: OrderData = from_json(order.order_data)?;
+    let destination_domain = order_destination_domain(deps, &order_data)?;
+    let destination_settler = DESTINATION_SETTLERS
+        .may_load(deps.storage, destination_domain)?
+        .ok_or(ContractError::UnknownDestinationDomain)?;
+    let destination_chain_id = domain_to_chain_id(deps, destination_domain)?;
+    order_data.destination_domain = Some(destination_domain);
+    order_data.destination_chain_id = Some(destination_chain_id);
+
+    let gateway_address = order_data_type.gateway.clone();
+    let nonce: u32 = deps
+        .querier
+        .query_wasm_smart(&gateway_address, &GatewayQueryMsg::NextNonce {})?;
+    let source_domain: u32 = deps
+        .querier
+        .query_wasm_smart(&gateway_address, &GatewayQueryMsg::LocalDomain {})?;
+
+    let origin_chain_id = domain_to_chain_id(deps, source_domain)?;
 
     let max_spent = vec![Output {
-        token: order_data.output_token,
+        token: token_to_bytes32(&order_data.output_token),
         amount: order_data.amount_out,
         recipient: order_data.recipient.clone(),
-        domain: order_data.destination_domain,
+        chain_id: destination_chain_id,
     }];
 
     let min_received = vec![Output {
-        token: order_data.input_token,
+        token: token_to_bytes32(&order_data.input_token),
         amount: order_data.amount_in,
         recipient: order_data.sender.clone(),
-        domain: order_data.source_domain,
+        chain_id: origin_chain_id,
     }];
 
-    let order = FastTransferOrder {
+    let fast_transfer_order = FastTransferOrder {
         sender: order_data.sender.clone(),
-        recipient: order_data.recipient,
+        recipient: order_data.recipient.clone(),
         amount_in: order_data.amount_in,
         amount_out: order_data.amount_out,
-        nonce: order_data.nonce,
-        source_domain: order_data.source_domain,
-        destination_domain: order_data.destination_domain,
-        timeout_timestamp: order_data.timeout_timestamp,
+        nonce,
+        source_domain,
+        destination_domain,
+        timeout_timestamp: order.fill_deadline.into(),
         data: order_data.data.clone(),
     };
 
     let fill_instructions = vec![FillInstruction {
-        destination_domain: order_data.destination_domain,
-        destination_settler: order_data.sender.clone(),
-        origin_data: to_json_binary(&order)?,
+        destination_chain_id,
+        destination_settler,
+        origin_data: HexBinary::from(fast_transfer_order.clone()).into(),
     }];
 
-    Ok(ResolvedCrossChainOrder {
-        user: order_data.sender,
-        origin_domain: order_data.source_domain,
-        open_deadline: u64::MAX,
-        fill_deadline: order.timeout_timestamp,
+    let resolved_order = ResolvedCrossChainOrder {
+        user: order_data.sender.clone(),
+        origin_chain_id,
+        open_deadline: u32::MAX,
+        fill_deadline: order.fill_deadline,
+        order_id: fast_transfer_order.id(),
         max_spent,
         min_received,
         fill_instructions,
+    };
+
+    Ok((resolved_order, order_data, order_data_type))
+}
+
+pub fn get_config(deps: Deps) -> StdResult<Config> {
+    Ok(Config {
+        gateway_address: GATEWAY_ADDRESS.load(deps.storage)?,
     })
 }
+
+pub fn get_destination_settlers(deps: Deps) -> StdResult<Vec<DestinationSettler>> {
+    DESTINATION_SETTLERS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|entry| {
+            let (domain, settler) = entry?;
+
+            Ok(DestinationSettler { domain, settler })
+        })
+        .collect()
+}
+
+pub fn get_deposits(deps: Deps, user: String) -> StdResult<Vec<Coin>> {
+    let user = deps.api.addr_validate(&user)?;
+
+    DEPOSITS
+        .prefix(user)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|entry| {
+            let (denom, amount) = entry?;
+
+            Ok(Coin { denom, amount })
+        })
+        .collect()
+}
+
+pub fn get_order_by_id(deps: Deps, order_id: HexBinary) -> StdResult<OpenedOrder> {
+    state::opened_orders().by_order_id(deps, order_id)
+}
+
+pub fn get_orders_by_user(
+    deps: Deps,
+    user: String,
+    start_after: Option<HexBinary>,
+    limit: Option<u32>,
+) -> StdResult<Vec<OpenedOrder>> {
+    let user = deps.api.addr_validate(&user)?;
+    let start_after = start_after.map(|x| x.to_vec());
+    state::opened_orders().by_user(deps, user, start_after, limit)
+}
+
+pub fn get_auto_settled_fills(
+    deps: Deps,
+    filler: String,
+    start_after: Option<HexBinary>,
+    limit: Option<u32>,
+) -> StdResult<Vec<AutoSettledFill>> {
+    let filler = deps.api.addr_validate(&filler)?;
+    let limit = limit.unwrap_or(10) as usize;
+    let start = start_after.map(|order_id| Bound::exclusive(order_id.to_vec()));
+
+    AUTO_SETTLED_FILLS
+        .prefix(filler)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|entry| Ok(entry?.1))
+        .collect()
+}
+
+/// Fill and settlement state of an order, as tracked by the gateway it was
+/// opened with. Orders not opened through this settler are looked up on the
+/// default gateway.
+pub fn get_order_status(deps: Deps, order_id: HexBinary) -> StdResult<OrderStatusResponse> {
+    let gateway_address =
+        match state::opened_orders().may_load_by_order_id(deps, order_id.clone())? {
+            Some(order) => order.gateway,
+            None => GATEWAY_ADDRESS.load(deps.storage)?,
+        };
+
+    deps.querier
+        .query_wasm_smart(&gateway_address, &GatewayQueryMsg::OrderStatus { order_id })
+}
+
+pub fn get_order_data_types(deps: Deps) -> StdResult<Vec<OrderDataType>> {
+    ORDER_DATA_TYPES
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|entry| Ok(entry?.1))
+        .collect()
+}
+
+pub fn get_chain_domains(deps: Deps) -> StdResult<Vec<ChainDomain>> {
+    CHAIN_DOMAINS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|entry| {
+            let (chain_id, domain) = entry?;
+
+            Ok(ChainDomain { chain_id, domain })
+        })
+        .collect()
+}
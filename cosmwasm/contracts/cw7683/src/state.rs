@@ -1,4 +1,33 @@
-use cosmwasm_std::Addr;
-use cw_storage_plus::Item;
+use cosmwasm_std::{Addr, Empty, HexBinary, Uint128};
+use cw_storage_plus::{Item, Map};
+
+use crate::{
+    orders::Orders,
+    types::{AutoSettledFill, OrderDataType},
+};
 
 pub const GATEWAY_ADDRESS: Item<Addr> = Item::new("gateway_address");
+
+pub const ORDER_DATA_TYPES: Map<Vec<u8>, OrderDataType> = Map::new("order_data_types");
+
+/// ERC-7683 chain IDs and the Hyperlane domains they map to, kept one to one.
+pub const CHAIN_DOMAINS: Map<u64, u32> = Map::new("chain_domains");
+pub const DOMAIN_CHAIN_IDS: Map<u32, u64> = Map::new("domain_chain_ids");
+
+pub const DESTINATION_SETTLERS: Map<u32, HexBinary> = Map::new("destination_settlers");
+
+pub const USED_NONCES: Map<(Addr, u64), Empty> = Map::new("used_nonces");
+
+pub const DEPOSITS: Map<(Addr, String), Uint128> = Map::new("deposits");
+
+/// Order ID resolved for the order being submitted to the gateway, checked
+/// against the ID the gateway returns in the open reply.
+pub const OPENING_ORDER_ID: Item<HexBinary> = Item::new("opening_order_id");
+
+/// Auto settled fills by the solver that made them and order ID.
+pub const AUTO_SETTLED_FILLS: Map<(Addr, Vec<u8>), AutoSettledFill> =
+    Map::new("auto_settled_fills");
+
+pub fn opened_orders() -> Orders<'static> {
+    Orders::new("opened_orders", "opened_orders_user_index")
+}
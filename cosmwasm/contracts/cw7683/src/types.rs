@@ -1,39 +1,199 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Binary, HexBinary, Uint128};
+use cosmwasm_std::{from_json, Addr, Binary, HexBinary, StdResult, Uint128};
+
+use crate::abi::{self, Token};
+
+#[cw_serde]
+pub struct Config {
+    /// Default gateway, used for fills and for the Go Fast order data type
+    /// registered at instantiation.
+    pub gateway_address: Addr,
+}
 
 #[cw_serde]
 pub struct OnchainCrossChainOrder {
-    pub fill_deadline: u64,
-    pub order_data_type: String,
+    pub fill_deadline: u32,
+    pub order_data_type: HexBinary,
     pub order_data: Binary,
 }
 
+/// Order signed by `user` and opened on their behalf through `OpenFor`.
+#[cw_serde]
+pub struct GaslessCrossChainOrder {
+    pub origin_settler: String,
+    pub user: String,
+    pub nonce: u64,
+    pub origin_chain_id: u64,
+    pub open_deadline: u32,
+    pub fill_deadline: u32,
+    pub order_data_type: HexBinary,
+    pub order_data: Binary,
+}
+
+/// Signature over the JSON encoding of a `GaslessCrossChainOrder`. `Secp256k1`
+/// signs it as the data of an ADR-36 sign doc, `EthSecp256k1` as an EIP-191
+/// personal message.
+#[cw_serde]
+pub enum OrderSignature {
+    Secp256k1 { pub_key: Binary, signature: Binary },
+    EthSecp256k1 { signature: Binary },
+}
+
 #[cw_serde]
 pub struct ResolvedCrossChainOrder {
     pub user: HexBinary,
-    pub origin_domain: u32,
-    pub open_deadline: u64,
-    pub fill_deadline: u64,
+    pub origin_chain_id: u64,
+    pub open_deadline: u32,
+    pub fill_deadline: u32,
+    pub order_id: HexBinary,
     pub max_spent: Vec<Output>,
     pub min_received: Vec<Output>,
     pub fill_instructions: Vec<FillInstruction>,
 }
 
+impl ResolvedCrossChainOrder {
+    /// Encodes the order as `abi.encode(resolvedOrder)`, the data of the
+    /// ERC-7683 `Open` event emitted by EVM settlers.
+    pub fn abi_encode(&self) -> StdResult<HexBinary> {
+        Ok(abi::encode(&Token::Tuple(vec![
+            Token::bytes32(&self.user)?,
+            Token::uint(self.origin_chain_id),
+            Token::uint(self.open_deadline.into()),
+            Token::uint(self.fill_deadline.into()),
+            Token::bytes32(&self.order_id)?,
+            Token::Array(
+                self.max_spent
+                    .iter()
+                    .map(Output::abi_token)
+                    .collect::<StdResult<_>>()?,
+            ),
+            Token::Array(
+                self.min_received
+                    .iter()
+                    .map(Output::abi_token)
+                    .collect::<StdResult<_>>()?,
+            ),
+            Token::Array(
+                self.fill_instructions
+                    .iter()
+                    .map(FillInstruction::abi_token)
+                    .collect::<StdResult<_>>()?,
+            ),
+        ]))
+        .into())
+    }
+}
+
 #[cw_serde]
 pub struct Output {
-    pub token: String,
+    pub token: HexBinary,
     pub amount: Uint128,
     pub recipient: HexBinary,
-    pub domain: u32,
+    pub chain_id: u64,
+}
+
+impl Output {
+    fn abi_token(&self) -> StdResult<Token> {
+        Ok(Token::Tuple(vec![
+            Token::bytes32(&self.token)?,
+            Token::uint128(self.amount),
+            Token::bytes32(&self.recipient)?,
+            Token::uint(self.chain_id),
+        ]))
+    }
 }
 
 #[cw_serde]
 pub struct FillInstruction {
-    pub destination_domain: u32,
+    pub destination_chain_id: u64,
     pub destination_settler: HexBinary,
     pub origin_data: Binary,
 }
 
+impl FillInstruction {
+    fn abi_token(&self) -> StdResult<Token> {
+        Ok(Token::Tuple(vec![
+            Token::uint(self.destination_chain_id),
+            Token::bytes32(&self.destination_settler)?,
+            Token::Bytes(self.origin_data.to_vec()),
+        ]))
+    }
+}
+
+/// JSON `filler_data` accepted by `Fill`. An empty `filler_data` fills with
+/// the defaults.
+#[cw_serde]
+#[derive(Default)]
+pub struct FillerData {
+    /// Address repaid on the origin domain when auto settling. Defaults to
+    /// the filler's own address, and must be left unset without `auto_settle`.
+    pub repayment_address: Option<HexBinary>,
+    /// Initiates settlement of the order in the same transaction as the fill.
+    /// Funds sent on top of the order's output pay for the settlement message.
+    #[serde(default)]
+    pub auto_settle: bool,
+    /// Order data type whose gateway fills the order. Defaults to the gateway
+    /// the settler was instantiated with.
+    pub order_data_type: Option<HexBinary>,
+}
+
+/// Fill made with `auto_settle`. The gateway records this settler as the
+/// filler, so only `filler` can retry its settlement through the settler.
+#[cw_serde]
+pub struct AutoSettledFill {
+    pub order_id: HexBinary,
+    pub filler: Addr,
+    pub gateway: Addr,
+    pub repayment_address: HexBinary,
+}
+
+/// Order opened through this settler, by `user` directly or on their behalf.
+#[cw_serde]
+pub struct OpenedOrder {
+    pub order_id: HexBinary,
+    pub user: Addr,
+    pub gateway: Addr,
+    pub resolved_order: ResolvedCrossChainOrder,
+}
+
+/// Decodes the `order_data` of an order data type into the order submitted
+/// to its gateway.
+#[cw_serde]
+pub enum OrderDataDecoder {
+    /// JSON encoded `OrderData`, see `GO_FAST_JSON_ORDER_DATA_TYPE`. Not
+    /// the ABI encoding used by the Solidity settler.
+    GoFast,
+}
+
+impl OrderDataDecoder {
+    pub fn decode(&self, order_data: &Binary) -> StdResult<OrderData> {
+        match self {
+            Self::GoFast => from_json(order_data),
+        }
+    }
+}
+
+/// An `order_data_type` accepted by the settler and the Go Fast deployment
+/// its orders are routed to.
+#[cw_serde]
+pub struct OrderDataType {
+    pub order_data_type: HexBinary,
+    pub gateway: Addr,
+    pub decoder: OrderDataDecoder,
+}
+
+#[cw_serde]
+pub struct DestinationSettler {
+    pub domain: u32,
+    pub settler: HexBinary,
+}
+
+#[cw_serde]
+pub struct ChainDomain {
+    pub chain_id: u64,
+    pub domain: u32,
+}
+
 #[cw_serde]
 pub struct OrderData {
     pub sender: HexBinary,
@@ -42,9 +202,10 @@ pub struct OrderData {
     pub output_token: String,
     pub amount_in: Uint128,
     pub amount_out: Uint128,
-    pub source_domain: u32,
-    pub destination_domain: u32,
-    pub nonce: u32,
+    /// Hyperlane domain of the destination. Either it or the destination's
+    /// `destination_chain_id` must be set.
+    pub destination_domain: Option<u32>,
+    pub destination_chain_id: Option<u64>,
     pub timeout_timestamp: u64,
     pub data: Option<HexBinary>,
 }
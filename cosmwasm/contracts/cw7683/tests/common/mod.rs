@@ -1,8 +1,25 @@
 use cosmwasm_std::{
+    from_json,
     testing::{mock_dependencies, mock_env, MockApi, MockQuerier},
-    Addr, Env, MemoryStorage, OwnedDeps,
+    to_json_binary, Addr, ContractResult, Env, HexBinary, MemoryStorage, OwnedDeps, QuerierResult,
+    SystemResult, Uint128, WasmQuery,
 };
-use cw_7683::state::GATEWAY_ADDRESS;
+use cw_7683::{
+    helpers::go_fast_json_order_data_type,
+    state::{
+        CHAIN_DOMAINS, DESTINATION_SETTLERS, DOMAIN_CHAIN_IDS, GATEWAY_ADDRESS, ORDER_DATA_TYPES,
+    },
+    types::{OrderDataDecoder, OrderDataType},
+};
+use go_fast::gateway::{Config, OrderStatus, OrderStatusResponse, QueryMsg as GatewayQueryMsg};
+
+pub const GATEWAY_NEXT_NONCE: u32 = 1;
+pub const GATEWAY_LOCAL_DOMAIN: u32 = 2;
+pub const GATEWAY_TOKEN_DENOM: &str = "uusdc";
+
+pub const DESTINATION_DOMAIN: u32 = 1;
+pub const DESTINATION_SETTLER: &str =
+    "0000000000000000000000005b16cfb4fa672d351760a189278406013a61b231";
 
 pub fn default_instantiate() -> (OwnedDeps<MemoryStorage, MockApi, MockQuerier>, Env) {
     let mut deps = mock_dependencies();
@@ -14,5 +31,71 @@ pub fn default_instantiate() -> (OwnedDeps<MemoryStorage, MockApi, MockQuerier>,
         .save(deps.as_mut().storage, &Addr::unchecked("go-fast-gateway"))
         .unwrap();
 
+    ORDER_DATA_TYPES
+        .save(
+            deps.as_mut().storage,
+            go_fast_json_order_data_type().to_vec(),
+            &OrderDataType {
+                order_data_type: go_fast_json_order_data_type(),
+                gateway: Addr::unchecked("go-fast-gateway"),
+                decoder: OrderDataDecoder::GoFast,
+            },
+        )
+        .unwrap();
+
+    DESTINATION_SETTLERS
+        .save(
+            deps.as_mut().storage,
+            DESTINATION_DOMAIN,
+            &HexBinary::from_hex(DESTINATION_SETTLER).unwrap(),
+        )
+        .unwrap();
+
+    // both domains use their domain as chain ID until a test remaps them
+    for domain in [DESTINATION_DOMAIN, GATEWAY_LOCAL_DOMAIN] {
+        CHAIN_DOMAINS
+            .save(deps.as_mut().storage, domain.into(), &domain)
+            .unwrap();
+        DOMAIN_CHAIN_IDS
+            .save(deps.as_mut().storage, domain, &domain.into())
+            .unwrap();
+    }
+
+    let wasm_handler = |query: &WasmQuery| -> QuerierResult {
+        match query {
+            WasmQuery::Smart { contract_addr, msg }
+                if contract_addr.starts_with("go-fast-gateway") =>
+            {
+                let response = match from_json(msg).unwrap() {
+                    GatewayQueryMsg::Config {} => to_json_binary(&Config {
+                        token_denom: GATEWAY_TOKEN_DENOM.to_string(),
+                        address_prefix: "osmo".to_string(),
+                        mailbox_addr: "mailbox".to_string(),
+                        hook_addr: "hook".to_string(),
+                    }),
+                    GatewayQueryMsg::NextNonce {} => to_json_binary(&GATEWAY_NEXT_NONCE),
+                    GatewayQueryMsg::LocalDomain {} => to_json_binary(&GATEWAY_LOCAL_DOMAIN),
+                    GatewayQueryMsg::OrderStatus { order_id } => {
+                        to_json_binary(&OrderStatusResponse {
+                            order_id,
+                            status: OrderStatus::Filled,
+                            sender: HexBinary::from(vec![0; 32]),
+                            destination_domain: DESTINATION_DOMAIN,
+                            amount: Uint128::new(100_000_000),
+                            refund_address: None,
+                            ibc_origin: None,
+                        })
+                    }
+                    msg => panic!("Unsupported gateway query: {:?}", msg),
+                };
+
+                SystemResult::Ok(ContractResult::Ok(response.unwrap()))
+            }
+            _ => panic!("Unsupported query: {:?}", query),
+        }
+    };
+
+    deps.querier.update_wasm(wasm_handler);
+
     (deps, env)
 }
@@ -0,0 +1,146 @@
+use cosmwasm_std::{
+    from_json,
+    testing::{mock_dependencies, mock_env, mock_info},
+    Addr,
+};
+use cw2::ContractVersion;
+use cw_7683::{
+    helpers::go_fast_json_order_data_type,
+    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    types::{Config, OrderDataDecoder, OrderDataType},
+};
+use cw_ownable::{Action, Ownership};
+
+pub mod common;
+
+#[test]
+fn test_instantiate_queries() {
+    let mut deps = mock_dependencies();
+    let env = mock_env();
+
+    cw_7683::contract::instantiate(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("owner", &[]),
+        InstantiateMsg {
+            gateway_address: Addr::unchecked("go-fast-gateway"),
+        },
+    )
+    .unwrap();
+
+    let config: Config = from_json(
+        cw_7683::contract::query(deps.as_ref(), env.clone(), QueryMsg::Config {}).unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(config.gateway_address, Addr::unchecked("go-fast-gateway"));
+
+    let version: ContractVersion = from_json(
+        cw_7683::contract::query(deps.as_ref(), env.clone(), QueryMsg::ContractVersion {}).unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(version.contract, "cw-7683");
+    assert_eq!(version.version, env!("CARGO_PKG_VERSION"));
+
+    let ownership: Ownership<Addr> =
+        from_json(cw_7683::contract::query(deps.as_ref(), env, QueryMsg::Ownership {}).unwrap())
+            .unwrap();
+
+    assert_eq!(ownership.owner, Some(Addr::unchecked("owner")));
+}
+
+#[test]
+fn test_two_step_ownership_transfer() {
+    let (mut deps, env) = common::default_instantiate();
+
+    cw_ownable::initialize_owner(&mut deps.storage, &deps.api, Some("owner")).unwrap();
+
+    cw_7683::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("owner", &[]),
+        ExecuteMsg::UpdateOwnership(Action::TransferOwnership {
+            new_owner: "new-owner".to_string(),
+            expiry: None,
+        }),
+    )
+    .unwrap();
+
+    // the pending owner has no rights until they accept
+    let err = cw_7683::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("new-owner", &[]),
+        ExecuteMsg::UpdateGateway {
+            gateway_address: "go-fast-gateway-v2".to_string(),
+        },
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(err, "Caller is not the contract's current owner");
+
+    cw_7683::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("new-owner", &[]),
+        ExecuteMsg::UpdateOwnership(Action::AcceptOwnership),
+    )
+    .unwrap();
+
+    let ownership: Ownership<Addr> =
+        from_json(cw_7683::contract::query(deps.as_ref(), env, QueryMsg::Ownership {}).unwrap())
+            .unwrap();
+
+    assert_eq!(ownership.owner, Some(Addr::unchecked("new-owner")));
+    assert_eq!(ownership.pending_owner, None);
+}
+
+#[test]
+fn test_update_gateway() {
+    let (mut deps, env) = common::default_instantiate();
+
+    cw_ownable::initialize_owner(&mut deps.storage, &deps.api, Some("owner")).unwrap();
+
+    let msg = ExecuteMsg::UpdateGateway {
+        gateway_address: "go-fast-gateway-v2".to_string(),
+    };
+
+    let err = cw_7683::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("not-owner", &[]),
+        msg.clone(),
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(err, "Caller is not the contract's current owner");
+
+    cw_7683::contract::execute(deps.as_mut(), env.clone(), mock_info("owner", &[]), msg).unwrap();
+
+    let config: Config = from_json(
+        cw_7683::contract::query(deps.as_ref(), env.clone(), QueryMsg::Config {}).unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        config.gateway_address,
+        Addr::unchecked("go-fast-gateway-v2")
+    );
+
+    let order_data_types: Vec<OrderDataType> = from_json(
+        cw_7683::contract::query(deps.as_ref(), env, QueryMsg::OrderDataTypes {}).unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        order_data_types,
+        vec![OrderDataType {
+            order_data_type: go_fast_json_order_data_type(),
+            gateway: Addr::unchecked("go-fast-gateway-v2"),
+            decoder: OrderDataDecoder::GoFast,
+        }]
+    );
+}
@@ -0,0 +1,232 @@
+use cosmwasm_std::{
+    coin, from_json,
+    testing::{mock_info, MockApi, MockQuerier},
+    to_json_binary, CosmosMsg, Env, HexBinary, MemoryStorage, OwnedDeps, Uint128, WasmMsg,
+};
+use cw_7683::{
+    helpers::go_fast_json_order_data_type,
+    msg::{ExecuteMsg, QueryMsg},
+    types::{ChainDomain, OnchainCrossChainOrder, OrderData},
+};
+use go_fast::{
+    gateway::ExecuteMsg as GatewayExecuteMsg,
+    helpers::{bech32_decode, left_pad_bytes},
+};
+
+pub mod common;
+
+const DESTINATION_CHAIN_ID: u64 = 42161;
+const ORIGIN_CHAIN_ID: u64 = 1_000_002;
+
+fn set_chain_domain(
+    deps: &mut OwnedDeps<MemoryStorage, MockApi, MockQuerier>,
+    env: &Env,
+    chain_id: u64,
+    domain: u32,
+) {
+    cw_7683::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("owner", &[]),
+        ExecuteMsg::SetChainDomain { chain_id, domain },
+    )
+    .unwrap();
+}
+
+fn order_with_destination(
+    user_hex: HexBinary,
+    fill_deadline: u32,
+    destination_domain: Option<u32>,
+    destination_chain_id: Option<u64>,
+) -> OnchainCrossChainOrder {
+    OnchainCrossChainOrder {
+        fill_deadline,
+        order_data_type: go_fast_json_order_data_type(),
+        order_data: to_json_binary(&OrderData {
+            sender: user_hex.clone(),
+            recipient: user_hex,
+            input_token: "uusdc".to_string(),
+            output_token: "uusdc".to_string(),
+            amount_in: Uint128::new(100_000_000),
+            amount_out: Uint128::new(98_000_000),
+            destination_domain,
+            destination_chain_id,
+            timeout_timestamp: fill_deadline.into(),
+            data: None,
+        })
+        .unwrap(),
+    }
+}
+
+#[test]
+fn test_set_chain_domain() {
+    let (mut deps, env) = common::default_instantiate();
+
+    cw_ownable::initialize_owner(&mut deps.storage, &deps.api, Some("owner")).unwrap();
+
+    let err = cw_7683::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("not-owner", &[]),
+        ExecuteMsg::SetChainDomain {
+            chain_id: DESTINATION_CHAIN_ID,
+            domain: common::DESTINATION_DOMAIN,
+        },
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(err, "Caller is not the contract's current owner");
+
+    set_chain_domain(
+        &mut deps,
+        &env,
+        DESTINATION_CHAIN_ID,
+        common::DESTINATION_DOMAIN,
+    );
+    set_chain_domain(
+        &mut deps,
+        &env,
+        ORIGIN_CHAIN_ID,
+        common::GATEWAY_LOCAL_DOMAIN,
+    );
+
+    // remapping the domain drops the chain ID it was mapped from
+    set_chain_domain(&mut deps, &env, 10, common::DESTINATION_DOMAIN);
+
+    let chain_domains: Vec<ChainDomain> = from_json(
+        cw_7683::contract::query(deps.as_ref(), env.clone(), QueryMsg::ChainDomains {}).unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        chain_domains,
+        vec![
+            ChainDomain {
+                chain_id: 10,
+                domain: common::DESTINATION_DOMAIN,
+            },
+            ChainDomain {
+                chain_id: ORIGIN_CHAIN_ID,
+                domain: common::GATEWAY_LOCAL_DOMAIN,
+            },
+        ]
+    );
+
+    cw_7683::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("owner", &[]),
+        ExecuteMsg::RemoveChainDomain { chain_id: 10 },
+    )
+    .unwrap();
+
+    let chain_domains: Vec<ChainDomain> =
+        from_json(cw_7683::contract::query(deps.as_ref(), env, QueryMsg::ChainDomains {}).unwrap())
+            .unwrap();
+
+    assert_eq!(chain_domains.len(), 1);
+}
+
+#[test]
+fn test_open_order_with_destination_chain_id() {
+    let (mut deps, env) = common::default_instantiate();
+
+    cw_ownable::initialize_owner(&mut deps.storage, &deps.api, Some("owner")).unwrap();
+
+    set_chain_domain(
+        &mut deps,
+        &env,
+        DESTINATION_CHAIN_ID,
+        common::DESTINATION_DOMAIN,
+    );
+    set_chain_domain(
+        &mut deps,
+        &env,
+        ORIGIN_CHAIN_ID,
+        common::GATEWAY_LOCAL_DOMAIN,
+    );
+
+    let user = deps.api.addr_make("user");
+    let user_hex = HexBinary::from(left_pad_bytes(bech32_decode(user.as_str()).unwrap(), 32));
+
+    let order = order_with_destination(
+        user_hex,
+        env.block.time.seconds() as u32 + 1000,
+        None,
+        Some(DESTINATION_CHAIN_ID),
+    );
+
+    let resolved_order = cw_7683::query::resolve(deps.as_ref(), order.clone()).unwrap();
+
+    assert_eq!(resolved_order.origin_chain_id, ORIGIN_CHAIN_ID);
+    assert_eq!(resolved_order.max_spent[0].chain_id, DESTINATION_CHAIN_ID);
+    assert_eq!(resolved_order.min_received[0].chain_id, ORIGIN_CHAIN_ID);
+    assert_eq!(
+        resolved_order.fill_instructions[0].destination_chain_id,
+        DESTINATION_CHAIN_ID
+    );
+
+    let res = cw_7683::contract::execute(
+        deps.as_mut(),
+        env,
+        mock_info(user.as_str(), &[coin(100_000_000, "uusdc")]),
+        ExecuteMsg::Open { order },
+    )
+    .unwrap();
+
+    let CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) = &res.messages[0].msg else {
+        panic!("unexpected message: {:?}", res.messages[0].msg);
+    };
+
+    match from_json(msg).unwrap() {
+        GatewayExecuteMsg::SubmitOrder {
+            destination_domain, ..
+        } => assert_eq!(destination_domain, common::DESTINATION_DOMAIN),
+        msg => panic!("unexpected gateway message: {msg:?}"),
+    }
+}
+
+#[test]
+fn test_resolve_fails_on_invalid_destination() {
+    let (mut deps, env) = common::default_instantiate();
+
+    cw_ownable::initialize_owner(&mut deps.storage, &deps.api, Some("owner")).unwrap();
+
+    set_chain_domain(
+        &mut deps,
+        &env,
+        DESTINATION_CHAIN_ID,
+        common::DESTINATION_DOMAIN,
+    );
+
+    let user_hex = HexBinary::from(vec![1; 32]);
+    let fill_deadline = env.block.time.seconds() as u32 + 1000;
+
+    for (destination_domain, destination_chain_id, expected) in [
+        (None, Some(1), "Unknown chain id: 1"),
+        (
+            Some(3),
+            Some(DESTINATION_CHAIN_ID),
+            "Destination domain does not match destination chain id",
+        ),
+        (
+            None,
+            None,
+            "Order data has no destination domain or chain id",
+        ),
+    ] {
+        let order = order_with_destination(
+            user_hex.clone(),
+            fill_deadline,
+            destination_domain,
+            destination_chain_id,
+        );
+
+        let err = cw_7683::query::resolve(deps.as_ref(), order)
+            .unwrap_err()
+            .to_string();
+
+        assert_eq!(err, expected);
+    }
+}
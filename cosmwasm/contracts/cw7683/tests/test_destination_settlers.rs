@@ -0,0 +1,69 @@
+use cosmwasm_std::{from_json, testing::mock_info, HexBinary};
+use cw_7683::{
+    msg::{ExecuteMsg, QueryMsg},
+    types::DestinationSettler,
+};
+
+pub mod common;
+
+#[test]
+fn test_add_destination_settler() {
+    let (mut deps, env) = common::default_instantiate();
+
+    cw_ownable::initialize_owner(&mut deps.storage, &deps.api, Some("owner")).unwrap();
+
+    let settler =
+        HexBinary::from_hex("000000000000000000000000aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa")
+            .unwrap();
+
+    let msg = ExecuteMsg::AddDestinationSettler {
+        domain: 10,
+        settler: settler.clone(),
+    };
+
+    let err = cw_7683::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("not-owner", &[]),
+        msg.clone(),
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(err, "Caller is not the contract's current owner");
+
+    let err = cw_7683::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("owner", &[]),
+        ExecuteMsg::AddDestinationSettler {
+            domain: 10,
+            settler: HexBinary::from(vec![0xaa; 20]),
+        },
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(err, "Invalid destination settler: expected 32 bytes");
+
+    cw_7683::contract::execute(deps.as_mut(), env.clone(), mock_info("owner", &[]), msg).unwrap();
+
+    let settlers: Vec<DestinationSettler> = from_json(
+        cw_7683::contract::query(deps.as_ref(), env, QueryMsg::DestinationSettlers {}).unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        settlers,
+        vec![
+            DestinationSettler {
+                domain: common::DESTINATION_DOMAIN,
+                settler: HexBinary::from_hex(common::DESTINATION_SETTLER).unwrap(),
+            },
+            DestinationSettler {
+                domain: 10,
+                settler,
+            },
+        ]
+    );
+}
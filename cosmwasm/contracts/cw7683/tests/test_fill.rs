@@ -1,7 +1,11 @@
 use cosmwasm_std::{
-    coin, testing::mock_info, to_json_binary, Binary, HexBinary, SubMsg, Uint128, WasmMsg,
+    coin, from_json, testing::mock_info, to_json_binary, Addr, Binary, HexBinary, SubMsg, Uint128,
+    WasmMsg,
+};
+use cw_7683::{
+    msg::{ExecuteMsg, QueryMsg},
+    types::{AutoSettledFill, FillerData},
 };
-use cw_7683::msg::ExecuteMsg;
 use go_fast::{
     gateway::ExecuteMsg as GatewayExecuteMsg,
     helpers::{bech32_decode, left_pad_bytes},
@@ -9,6 +13,7 @@ use go_fast::{
 };
 
 pub mod common;
+
 #[test]
 fn test_fill() {
     let (mut deps, env) = common::default_instantiate();
@@ -60,3 +65,268 @@ fn test_fill() {
     assert_eq!(res.messages.len(), 1);
     assert_eq!(res.messages[0], expected_msg);
 }
+
+fn fill_order(user_address: &Addr, timeout_timestamp: u64) -> FastTransferOrder {
+    let user_address_hex = HexBinary::from(left_pad_bytes(
+        bech32_decode(user_address.as_str()).unwrap(),
+        32,
+    ));
+
+    FastTransferOrder {
+        sender: user_address_hex.clone(),
+        recipient: user_address_hex,
+        amount_in: Uint128::new(100_000_000),
+        amount_out: Uint128::new(98_000_000),
+        source_domain: 2,
+        destination_domain: 1,
+        nonce: 1,
+        timeout_timestamp,
+        data: None,
+    }
+}
+
+#[test]
+fn test_fill_with_auto_settle() {
+    let (mut deps, env) = common::default_instantiate();
+
+    let order = fill_order(&deps.api.addr_make("user"), env.block.time.seconds() + 1000);
+
+    let repayment_address = HexBinary::from(vec![7; 32]);
+
+    let info = mock_info(
+        "solver",
+        &[
+            coin(order.amount_out.u128() + 500, "uusdc"),
+            coin(1_000, "uosmo"),
+        ],
+    );
+
+    let res = cw_7683::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        info,
+        ExecuteMsg::Fill {
+            order_id: order.id(),
+            origin_data: HexBinary::from(order.clone()).into(),
+            filler_data: to_json_binary(&FillerData {
+                repayment_address: Some(repayment_address.clone()),
+                auto_settle: true,
+                order_data_type: None,
+            })
+            .unwrap(),
+        },
+    )
+    .unwrap();
+
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: "go-fast-gateway".to_string(),
+                msg: to_json_binary(&GatewayExecuteMsg::FillOrder {
+                    filler: env.contract.address,
+                    order: order.clone(),
+                })
+                .unwrap(),
+                funds: vec![coin(order.amount_out.u128(), "uusdc")],
+            }),
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: "go-fast-gateway".to_string(),
+                msg: to_json_binary(&GatewayExecuteMsg::InitiateSettlement {
+                    order_ids: vec![order.id()],
+                    repayment_address,
+                })
+                .unwrap(),
+                funds: vec![coin(500, "uusdc"), coin(1_000, "uosmo")],
+            }),
+        ]
+    );
+}
+
+#[test]
+fn test_fill_fails_on_order_id_mismatch() {
+    let (mut deps, env) = common::default_instantiate();
+
+    let order = fill_order(&deps.api.addr_make("user"), env.block.time.seconds() + 1000);
+
+    let err = cw_7683::contract::execute(
+        deps.as_mut(),
+        env,
+        mock_info("solver", &[coin(order.amount_out.u128(), "uusdc")]),
+        ExecuteMsg::Fill {
+            order_id: HexBinary::from(vec![1; 32]),
+            origin_data: HexBinary::from(order).into(),
+            filler_data: Binary::default(),
+        },
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(err, "Order ID does not match origin data");
+}
+
+#[test]
+fn test_fill_fails_on_invalid_origin_data() {
+    let (mut deps, env) = common::default_instantiate();
+
+    let origin_data = Binary::from(vec![1; 100]);
+
+    let err = cw_7683::contract::execute(
+        deps.as_mut(),
+        env,
+        mock_info("solver", &[coin(98_000_000, "uusdc")]),
+        ExecuteMsg::Fill {
+            order_id: go_fast::helpers::keccak256_hash(&origin_data),
+            origin_data,
+            filler_data: Binary::default(),
+        },
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(err, "Invalid origin data");
+}
+
+#[test]
+fn test_fill_fails_on_unexpected_funds() {
+    let (mut deps, env) = common::default_instantiate();
+
+    let order = fill_order(&deps.api.addr_make("user"), env.block.time.seconds() + 1000);
+
+    for funds in [
+        vec![coin(order.amount_out.u128() - 1, "uusdc")],
+        vec![coin(order.amount_out.u128(), "uosmo")],
+        // extra funds are only accepted to pay for auto settlement
+        vec![coin(order.amount_out.u128() + 1, "uusdc")],
+    ] {
+        let err = cw_7683::contract::execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("solver", &funds),
+            ExecuteMsg::Fill {
+                order_id: order.id(),
+                origin_data: HexBinary::from(order.clone()).into(),
+                filler_data: Binary::default(),
+            },
+        )
+        .unwrap_err()
+        .to_string();
+
+        assert!(err.starts_with("Unexpected funds sent"), "{err}");
+    }
+}
+
+#[test]
+fn test_fill_fails_on_repayment_address_without_auto_settle() {
+    let (mut deps, env) = common::default_instantiate();
+
+    let order = fill_order(&deps.api.addr_make("user"), env.block.time.seconds() + 1000);
+
+    let err = cw_7683::contract::execute(
+        deps.as_mut(),
+        env,
+        mock_info("solver", &[coin(order.amount_out.u128(), "uusdc")]),
+        ExecuteMsg::Fill {
+            order_id: order.id(),
+            origin_data: HexBinary::from(order).into(),
+            filler_data: to_json_binary(&FillerData {
+                repayment_address: Some(HexBinary::from(vec![1; 32])),
+                ..FillerData::default()
+            })
+            .unwrap(),
+        },
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(err, "Repayment address can only be set when auto settling");
+}
+
+#[test]
+fn test_retry_auto_settlement() {
+    let (mut deps, env) = common::default_instantiate();
+
+    let solver = deps.api.addr_make("solver");
+    let order = fill_order(&deps.api.addr_make("user"), env.block.time.seconds() + 1000);
+    let repayment_address = HexBinary::from(vec![7; 32]);
+
+    cw_7683::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(solver.as_str(), &[coin(order.amount_out.u128(), "uusdc")]),
+        ExecuteMsg::Fill {
+            order_id: order.id(),
+            origin_data: HexBinary::from(order.clone()).into(),
+            filler_data: to_json_binary(&FillerData {
+                repayment_address: Some(repayment_address.clone()),
+                auto_settle: true,
+                order_data_type: None,
+            })
+            .unwrap(),
+        },
+    )
+    .unwrap();
+
+    let fills: Vec<AutoSettledFill> = from_json(
+        cw_7683::contract::query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::AutoSettledFills {
+                filler: solver.to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        fills,
+        vec![AutoSettledFill {
+            order_id: order.id(),
+            filler: solver.clone(),
+            gateway: Addr::unchecked("go-fast-gateway"),
+            repayment_address: repayment_address.clone(),
+        }]
+    );
+
+    let retry_msg = ExecuteMsg::RetrySettlement {
+        order_id: order.id(),
+    };
+
+    let res = cw_7683::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("not_solver", &[]),
+        retry_msg.clone(),
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(
+        res,
+        format!("No auto settled fill of order {} by the sender", order.id())
+    );
+
+    let res = cw_7683::contract::execute(
+        deps.as_mut(),
+        env,
+        mock_info(solver.as_str(), &[coin(500, "uosmo")]),
+        retry_msg,
+    )
+    .unwrap();
+
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(WasmMsg::Execute {
+            contract_addr: "go-fast-gateway".to_string(),
+            msg: to_json_binary(&GatewayExecuteMsg::InitiateSettlement {
+                order_ids: vec![order.id()],
+                repayment_address,
+            })
+            .unwrap(),
+            funds: vec![coin(500, "uosmo")],
+        })]
+    );
+}
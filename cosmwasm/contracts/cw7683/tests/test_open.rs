@@ -1,12 +1,17 @@
-use cosmwasm_std::{coin, testing::mock_info, to_json_binary, HexBinary, SubMsg, Uint128, WasmMsg};
+use cosmwasm_std::{
+    coin, testing::mock_info, to_json_binary, Attribute, Binary, Event, HexBinary, Reply, SubMsg,
+    SubMsgResponse, SubMsgResult, Uint128, WasmMsg,
+};
 use cw_7683::{
-    contract::GO_FAST_ORDER_TYPE,
+    contract::OPEN_REPLY_ID,
+    helpers::go_fast_json_order_data_type,
     msg::ExecuteMsg,
     types::{OnchainCrossChainOrder, OrderData},
 };
 use go_fast::{
     gateway::ExecuteMsg as GatewayExecuteMsg,
     helpers::{bech32_decode, left_pad_bytes},
+    FastTransferOrder,
 };
 
 pub mod common;
@@ -31,21 +36,24 @@ fn test_open() {
         output_token: output_token.to_string(),
         amount_in: Uint128::new(100_000_000),
         amount_out: Uint128::new(98_000_000),
-        source_domain: 2,
-        destination_domain: 1,
-        nonce: 1,
+        destination_domain: Some(1),
+        destination_chain_id: None,
         timeout_timestamp: env.block.time.seconds() + 1000,
         data: None,
     };
 
     let order_data = to_json_binary(&order).unwrap();
 
+    let onchain_order = OnchainCrossChainOrder {
+        fill_deadline: env.block.time.seconds() as u32 + 1000,
+        order_data_type: go_fast_json_order_data_type(),
+        order_data,
+    };
+
+    let resolved_order = cw_7683::query::resolve(deps.as_ref(), onchain_order.clone()).unwrap();
+
     let msg = ExecuteMsg::Open {
-        order: OnchainCrossChainOrder {
-            fill_deadline: env.block.time.seconds() + 1000,
-            order_data_type: GO_FAST_ORDER_TYPE.to_string(),
-            order_data,
-        },
+        order: onchain_order,
     };
 
     let info = mock_info(
@@ -55,21 +63,143 @@ fn test_open() {
 
     let res = cw_7683::contract::execute(deps.as_mut(), env.clone(), info.clone(), msg).unwrap();
 
-    let expected_msg = SubMsg::new(WasmMsg::Execute {
-        contract_addr: "go-fast-gateway".to_string(),
-        msg: to_json_binary(&GatewayExecuteMsg::SubmitOrder {
-            sender: user_address_hex.clone(),
-            recipient: user_address_hex.clone(),
-            amount_in: order.amount_in,
-            amount_out: order.amount_out,
-            destination_domain: order.destination_domain,
-            timeout_timestamp: order.timeout_timestamp,
-            data: None,
-        })
-        .unwrap(),
-        funds: info.funds,
-    });
+    let expected_msg = SubMsg::reply_on_success(
+        WasmMsg::Execute {
+            contract_addr: "go-fast-gateway".to_string(),
+            msg: to_json_binary(&GatewayExecuteMsg::SubmitOrder {
+                sender: user_address_hex.clone(),
+                recipient: user_address_hex.clone(),
+                amount_in: order.amount_in,
+                amount_out: order.amount_out,
+                destination_domain: 1,
+                timeout_timestamp: order.timeout_timestamp,
+                data: None,
+                refund_address: None,
+            })
+            .unwrap(),
+            funds: info.funds,
+        },
+        OPEN_REPLY_ID,
+    );
 
     assert_eq!(res.messages.len(), 1);
     assert_eq!(res.messages[0], expected_msg);
+    assert_eq!(
+        res.events,
+        vec![Event::new("open")
+            .add_attribute("order_id", resolved_order.order_id.to_string())
+            .add_attribute(
+                "resolved_order",
+                resolved_order.abi_encode().unwrap().to_string()
+            )]
+    );
+
+    let expected_order_id = FastTransferOrder {
+        sender: user_address_hex.clone(),
+        recipient: user_address_hex,
+        amount_in: order.amount_in,
+        amount_out: order.amount_out,
+        nonce: common::GATEWAY_NEXT_NONCE,
+        source_domain: common::GATEWAY_LOCAL_DOMAIN,
+        destination_domain: 1,
+        timeout_timestamp: order.timeout_timestamp,
+        data: None,
+    }
+    .id();
+
+    assert_eq!(resolved_order.order_id, expected_order_id);
+
+    // MsgExecuteContractResponse { data: order_id }
+    let execute_response = |order_id: &[u8]| {
+        let mut execute_response = vec![0x0a, 0x20];
+        execute_response.extend_from_slice(order_id);
+        Binary::from(execute_response)
+    };
+
+    // the gateway assigning another ID fails the whole open
+    let err = cw_7683::contract::reply(
+        deps.as_mut(),
+        env.clone(),
+        Reply {
+            id: OPEN_REPLY_ID,
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![],
+                data: Some(execute_response(&[1; 32])),
+            }),
+        },
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(
+        err,
+        format!(
+            "Gateway returned order id {}, expected {}",
+            HexBinary::from(vec![1; 32]),
+            expected_order_id
+        )
+    );
+
+    let res = cw_7683::contract::reply(
+        deps.as_mut(),
+        env,
+        Reply {
+            id: OPEN_REPLY_ID,
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![],
+                data: Some(execute_response(&expected_order_id)),
+            }),
+        },
+    )
+    .unwrap();
+
+    assert_eq!(
+        res.attributes,
+        vec![Attribute::new("order_id", expected_order_id.to_string())]
+    );
+}
+
+#[test]
+fn test_open_rejects_impersonated_sender() {
+    let (mut deps, env) = common::default_instantiate();
+
+    let user_address = deps.api.addr_make("user");
+    let attacker_address = deps.api.addr_make("attacker");
+    let user_address_hex = HexBinary::from(left_pad_bytes(
+        bech32_decode(user_address.as_str()).unwrap(),
+        32,
+    ));
+
+    let order = OrderData {
+        sender: user_address_hex.clone(),
+        recipient: user_address_hex,
+        input_token: "uosmo".to_string(),
+        output_token: "uatom".to_string(),
+        amount_in: Uint128::new(100_000_000),
+        amount_out: Uint128::new(98_000_000),
+        destination_domain: Some(1),
+        destination_chain_id: None,
+        timeout_timestamp: env.block.time.seconds() + 1000,
+        data: None,
+    };
+
+    let res = cw_7683::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(
+            attacker_address.as_str(),
+            &[coin(order.amount_in.u128(), "uosmo")],
+        ),
+        ExecuteMsg::Open {
+            order: OnchainCrossChainOrder {
+                fill_deadline: env.block.time.seconds() as u32 + 1000,
+                order_data_type: go_fast_json_order_data_type(),
+                order_data: to_json_binary(&order).unwrap(),
+            },
+        },
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(res, "Order sender does not match user");
 }
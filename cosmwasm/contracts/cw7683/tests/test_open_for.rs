@@ -0,0 +1,381 @@
+use bech32::{Bech32, Hrp};
+use cosmwasm_std::{
+    coin, from_json, testing::mock_info, to_json_binary, to_json_vec, Addr, Binary, Coin,
+    HexBinary, SubMsg, Uint128, WasmMsg,
+};
+use cw_7683::{
+    contract::OPEN_REPLY_ID,
+    helpers::go_fast_json_order_data_type,
+    msg::{ExecuteMsg, QueryMsg},
+    types::{GaslessCrossChainOrder, OpenedOrder, OrderData, OrderSignature},
+};
+use go_fast::{
+    gateway::ExecuteMsg as GatewayExecuteMsg,
+    helpers::{bech32_decode, keccak256_hash, left_pad_bytes},
+};
+use k256::ecdsa::{signature::hazmat::PrehashSigner, Signature, SigningKey};
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256};
+
+pub mod common;
+
+fn bech32_encode(raw_addr: &[u8]) -> Addr {
+    Addr::unchecked(bech32::encode::<Bech32>(Hrp::parse("osmo").unwrap(), raw_addr).unwrap())
+}
+
+fn signing_key(seed: u8) -> SigningKey {
+    SigningKey::from_bytes(&[seed; 32].into()).unwrap()
+}
+
+fn secp256k1_user(key: &SigningKey) -> (Addr, Binary) {
+    let pub_key = key
+        .verifying_key()
+        .to_encoded_point(true)
+        .as_bytes()
+        .to_vec();
+    let canonical = Ripemd160::digest(Sha256::digest(&pub_key)).to_vec();
+
+    (bech32_encode(&canonical), Binary::from(pub_key))
+}
+
+fn eth_secp256k1_user(key: &SigningKey) -> Addr {
+    let pub_key = key
+        .verifying_key()
+        .to_encoded_point(false)
+        .as_bytes()
+        .to_vec();
+    let canonical = keccak256_hash(&pub_key[1..])[12..].to_vec();
+
+    bech32_encode(&canonical)
+}
+
+/// Signs `order` as the data of an ADR-36 sign doc, as Cosmos wallets do for
+/// `signArbitrary`.
+fn secp256k1_sign(key: &SigningKey, order: &GaslessCrossChainOrder) -> Binary {
+    let sign_doc = format!(
+        concat!(
+            r#"{{"account_number":"0","chain_id":"","fee":{{"amount":[],"gas":"0"}},"memo":"","#,
+            r#""msgs":[{{"type":"sign/MsgSignData","value":{{"data":"{}","signer":"{}"}}}}],"#,
+            r#""sequence":"0"}}"#
+        ),
+        to_json_binary(order).unwrap().to_base64(),
+        order.user
+    );
+
+    let signature: Signature = key.sign_prehash(&Sha256::digest(sign_doc)).unwrap();
+
+    Binary::from(signature.to_bytes().to_vec())
+}
+
+/// Signs `order` as an EIP-191 personal message, as Ethereum wallets do for
+/// `personal_sign`.
+fn eth_secp256k1_sign(key: &SigningKey, order: &GaslessCrossChainOrder) -> Binary {
+    let message = to_json_vec(order).unwrap();
+    let mut prefixed = format!("\x19Ethereum Signed Message:\n{}", message.len()).into_bytes();
+    prefixed.extend_from_slice(&message);
+
+    let (signature, recovery_id) = key
+        .sign_prehash_recoverable(&keccak256_hash(&prefixed))
+        .unwrap();
+
+    let mut signature = signature.to_bytes().to_vec();
+    signature.push(recovery_id.to_byte() + 27);
+
+    Binary::from(signature)
+}
+
+fn gasless_order(
+    settler: &Addr,
+    user: &Addr,
+    open_deadline: u32,
+) -> (GaslessCrossChainOrder, OrderData) {
+    let user_hex = HexBinary::from(left_pad_bytes(bech32_decode(user.as_str()).unwrap(), 32));
+
+    let order_data = OrderData {
+        sender: user_hex.clone(),
+        recipient: user_hex,
+        input_token: "uusdc".to_string(),
+        output_token: "uusdc".to_string(),
+        amount_in: Uint128::new(100_000_000),
+        amount_out: Uint128::new(98_000_000),
+        destination_domain: Some(common::DESTINATION_DOMAIN),
+        destination_chain_id: None,
+        timeout_timestamp: u64::from(open_deadline) + 1000,
+        data: None,
+    };
+
+    let order = GaslessCrossChainOrder {
+        origin_settler: settler.to_string(),
+        user: user.to_string(),
+        nonce: 1,
+        origin_chain_id: common::GATEWAY_LOCAL_DOMAIN.into(),
+        open_deadline,
+        fill_deadline: open_deadline + 1000,
+        order_data_type: go_fast_json_order_data_type(),
+        order_data: to_json_binary(&order_data).unwrap(),
+    };
+
+    (order, order_data)
+}
+
+#[test]
+fn test_open_for_with_secp256k1_signature() {
+    let (mut deps, env) = common::default_instantiate();
+
+    let key = signing_key(1);
+    let (user, pub_key) = secp256k1_user(&key);
+
+    let (order, order_data) = gasless_order(
+        &env.contract.address,
+        &user,
+        env.block.time.seconds() as u32 + 100,
+    );
+
+    // the relayer funds the deposit of a user without gas
+    cw_7683::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("relayer", &[coin(order_data.amount_in.u128(), "uusdc")]),
+        ExecuteMsg::Deposit {
+            recipient: Some(user.to_string()),
+        },
+    )
+    .unwrap();
+
+    let msg = ExecuteMsg::OpenFor {
+        order: order.clone(),
+        signature: OrderSignature::Secp256k1 {
+            pub_key,
+            signature: secp256k1_sign(&key, &order),
+        },
+        origin_filler_data: Binary::default(),
+    };
+
+    let res = cw_7683::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("relayer", &[]),
+        msg.clone(),
+    )
+    .unwrap();
+
+    let expected_msg = SubMsg::reply_on_success(
+        WasmMsg::Execute {
+            contract_addr: "go-fast-gateway".to_string(),
+            msg: to_json_binary(&GatewayExecuteMsg::SubmitOrder {
+                sender: order_data.sender.clone(),
+                recipient: order_data.recipient.clone(),
+                amount_in: order_data.amount_in,
+                amount_out: order_data.amount_out,
+                destination_domain: common::DESTINATION_DOMAIN,
+                timeout_timestamp: order.fill_deadline.into(),
+                data: None,
+                refund_address: None,
+            })
+            .unwrap(),
+            funds: vec![coin(order_data.amount_in.u128(), "uusdc")],
+        },
+        OPEN_REPLY_ID,
+    );
+
+    assert_eq!(res.messages, vec![expected_msg]);
+
+    let opened_order: OpenedOrder = from_json(
+        cw_7683::contract::query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::OrderById {
+                order_id: HexBinary::from_hex(&res.events[0].attributes[0].value).unwrap(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        opened_order.resolved_order.open_deadline,
+        order.open_deadline
+    );
+
+    let deposits: Vec<Coin> = from_json(
+        cw_7683::contract::query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::Deposits {
+                user: user.to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(deposits, vec![coin(0, "uusdc")]);
+
+    let err = cw_7683::contract::execute(deps.as_mut(), env, mock_info("relayer", &[]), msg)
+        .unwrap_err()
+        .to_string();
+
+    assert_eq!(err, "Nonce already used");
+}
+
+#[test]
+fn test_open_for_with_eth_secp256k1_signature() {
+    let (mut deps, env) = common::default_instantiate();
+
+    let key = signing_key(2);
+    let user = eth_secp256k1_user(&key);
+
+    let (order, order_data) = gasless_order(
+        &env.contract.address,
+        &user,
+        env.block.time.seconds() as u32 + 100,
+    );
+
+    cw_7683::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(user.as_str(), &[coin(order_data.amount_in.u128(), "uusdc")]),
+        ExecuteMsg::Deposit { recipient: None },
+    )
+    .unwrap();
+
+    let signature = eth_secp256k1_sign(&key, &order);
+
+    let res = cw_7683::contract::execute(
+        deps.as_mut(),
+        env,
+        mock_info("relayer", &[]),
+        ExecuteMsg::OpenFor {
+            order,
+            signature: OrderSignature::EthSecp256k1 { signature },
+            origin_filler_data: Binary::default(),
+        },
+    )
+    .unwrap();
+
+    assert_eq!(res.messages.len(), 1);
+}
+
+#[test]
+fn test_open_for_fails_on_invalid_signature() {
+    let (mut deps, env) = common::default_instantiate();
+
+    let key = signing_key(1);
+    let (user, pub_key) = secp256k1_user(&key);
+
+    let (order, _) = gasless_order(
+        &env.contract.address,
+        &user,
+        env.block.time.seconds() as u32 + 100,
+    );
+
+    let signature = secp256k1_sign(&signing_key(3), &order);
+
+    let err = cw_7683::contract::execute(
+        deps.as_mut(),
+        env,
+        mock_info("relayer", &[]),
+        ExecuteMsg::OpenFor {
+            order,
+            signature: OrderSignature::Secp256k1 { pub_key, signature },
+            origin_filler_data: Binary::default(),
+        },
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(err, "Invalid signature");
+}
+
+#[test]
+fn test_open_for_fails_after_open_deadline() {
+    let (mut deps, env) = common::default_instantiate();
+
+    let key = signing_key(1);
+    let (user, pub_key) = secp256k1_user(&key);
+
+    let (order, _) = gasless_order(
+        &env.contract.address,
+        &user,
+        env.block.time.seconds() as u32 - 1,
+    );
+
+    let signature = secp256k1_sign(&key, &order);
+
+    let err = cw_7683::contract::execute(
+        deps.as_mut(),
+        env,
+        mock_info("relayer", &[]),
+        ExecuteMsg::OpenFor {
+            order,
+            signature: OrderSignature::Secp256k1 { pub_key, signature },
+            origin_filler_data: Binary::default(),
+        },
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(err, "Open deadline passed");
+}
+
+#[test]
+fn test_open_for_fails_on_insufficient_deposit() {
+    let (mut deps, env) = common::default_instantiate();
+
+    let key = signing_key(1);
+    let (user, pub_key) = secp256k1_user(&key);
+
+    let (order, _) = gasless_order(
+        &env.contract.address,
+        &user,
+        env.block.time.seconds() as u32 + 100,
+    );
+
+    let signature = secp256k1_sign(&key, &order);
+
+    let err = cw_7683::contract::execute(
+        deps.as_mut(),
+        env,
+        mock_info("relayer", &[]),
+        ExecuteMsg::OpenFor {
+            order,
+            signature: OrderSignature::Secp256k1 { pub_key, signature },
+            origin_filler_data: Binary::default(),
+        },
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(err, "Insufficient deposit");
+}
+
+#[test]
+fn test_open_for_fails_on_origin_filler_data() {
+    let (mut deps, env) = common::default_instantiate();
+
+    let key = signing_key(1);
+    let (user, pub_key) = secp256k1_user(&key);
+
+    let (order, _) = gasless_order(
+        &env.contract.address,
+        &user,
+        env.block.time.seconds() as u32 + 100,
+    );
+
+    let signature = secp256k1_sign(&key, &order);
+
+    let err = cw_7683::contract::execute(
+        deps.as_mut(),
+        env,
+        mock_info("relayer", &[]),
+        ExecuteMsg::OpenFor {
+            order,
+            signature: OrderSignature::Secp256k1 { pub_key, signature },
+            origin_filler_data: Binary::from(b"filler".to_vec()),
+        },
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(err, "Origin filler data is not supported");
+}
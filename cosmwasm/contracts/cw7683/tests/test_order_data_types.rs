@@ -0,0 +1,131 @@
+use cosmwasm_std::{
+    coin, from_json, testing::mock_info, to_json_binary, Addr, HexBinary, Uint128, WasmMsg,
+};
+use cw_7683::{
+    helpers::go_fast_json_order_data_type,
+    msg::{ExecuteMsg, QueryMsg},
+    types::{OnchainCrossChainOrder, OpenedOrder, OrderData, OrderDataDecoder, OrderDataType},
+};
+use go_fast::helpers::{bech32_decode, keccak256_hash, left_pad_bytes};
+
+pub mod common;
+
+#[test]
+fn test_orders_are_routed_to_the_gateway_of_their_type() {
+    let (mut deps, env) = common::default_instantiate();
+
+    cw_ownable::initialize_owner(&mut deps.storage, &deps.api, Some("owner")).unwrap();
+
+    let v2_order_data_type = keccak256_hash(b"GoFastOrderDataV2");
+
+    let msg = ExecuteMsg::AddOrderDataType {
+        order_data_type: v2_order_data_type.clone(),
+        gateway_address: "go-fast-gateway-v2".to_string(),
+        decoder: OrderDataDecoder::GoFast,
+    };
+
+    let err = cw_7683::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("not-owner", &[]),
+        msg.clone(),
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(err, "Caller is not the contract's current owner");
+
+    cw_7683::contract::execute(deps.as_mut(), env.clone(), mock_info("owner", &[]), msg).unwrap();
+
+    let mut order_data_types: Vec<OrderDataType> = from_json(
+        cw_7683::contract::query(deps.as_ref(), env.clone(), QueryMsg::OrderDataTypes {}).unwrap(),
+    )
+    .unwrap();
+    order_data_types.sort_by_key(|t| t.gateway.clone());
+
+    assert_eq!(
+        order_data_types,
+        vec![
+            OrderDataType {
+                order_data_type: go_fast_json_order_data_type(),
+                gateway: Addr::unchecked("go-fast-gateway"),
+                decoder: OrderDataDecoder::GoFast,
+            },
+            OrderDataType {
+                order_data_type: v2_order_data_type.clone(),
+                gateway: Addr::unchecked("go-fast-gateway-v2"),
+                decoder: OrderDataDecoder::GoFast,
+            },
+        ]
+    );
+
+    let user = deps.api.addr_make("user");
+    let user_hex = HexBinary::from(left_pad_bytes(bech32_decode(user.as_str()).unwrap(), 32));
+
+    let order = OnchainCrossChainOrder {
+        fill_deadline: env.block.time.seconds() as u32 + 1000,
+        order_data_type: v2_order_data_type.clone(),
+        order_data: to_json_binary(&OrderData {
+            sender: user_hex.clone(),
+            recipient: user_hex,
+            input_token: "uusdc".to_string(),
+            output_token: "uusdc".to_string(),
+            amount_in: Uint128::new(100_000_000),
+            amount_out: Uint128::new(98_000_000),
+            destination_domain: Some(common::DESTINATION_DOMAIN),
+            destination_chain_id: None,
+            timeout_timestamp: env.block.time.seconds() + 1000,
+            data: None,
+        })
+        .unwrap(),
+    };
+
+    let res = cw_7683::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(user.as_str(), &[coin(100_000_000, "uusdc")]),
+        ExecuteMsg::Open {
+            order: order.clone(),
+        },
+    )
+    .unwrap();
+
+    match &res.messages[0].msg {
+        cosmwasm_std::CosmosMsg::Wasm(WasmMsg::Execute { contract_addr, .. }) => {
+            assert_eq!(contract_addr, "go-fast-gateway-v2")
+        }
+        msg => panic!("unexpected message: {msg:?}"),
+    }
+
+    let opened: Vec<OpenedOrder> = from_json(
+        cw_7683::contract::query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::OrdersByUser {
+                user: user.to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(opened[0].gateway, Addr::unchecked("go-fast-gateway-v2"));
+
+    cw_7683::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("owner", &[]),
+        ExecuteMsg::RemoveOrderDataType {
+            order_data_type: v2_order_data_type,
+        },
+    )
+    .unwrap();
+
+    let err = cw_7683::query::resolve(deps.as_ref(), order)
+        .unwrap_err()
+        .to_string();
+
+    assert_eq!(err, "Wrong order data type");
+}
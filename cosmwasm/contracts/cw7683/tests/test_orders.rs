@@ -0,0 +1,155 @@
+use cosmwasm_std::{coin, from_json, testing::mock_info, to_json_binary, Addr, HexBinary, Uint128};
+use cw_7683::{
+    helpers::go_fast_json_order_data_type,
+    msg::{ExecuteMsg, QueryMsg},
+    types::{OnchainCrossChainOrder, OpenedOrder, OrderData},
+};
+use go_fast::{
+    gateway::{OrderStatus, OrderStatusResponse},
+    helpers::{bech32_decode, left_pad_bytes},
+};
+
+pub mod common;
+
+fn onchain_order(
+    user_hex: HexBinary,
+    amount_in: u128,
+    fill_deadline: u32,
+) -> OnchainCrossChainOrder {
+    let order_data = OrderData {
+        sender: user_hex.clone(),
+        recipient: user_hex,
+        input_token: "uosmo".to_string(),
+        output_token: "uatom".to_string(),
+        amount_in: Uint128::new(amount_in),
+        amount_out: Uint128::new(amount_in - 1_000),
+        destination_domain: Some(common::DESTINATION_DOMAIN),
+        destination_chain_id: None,
+        timeout_timestamp: fill_deadline.into(),
+        data: None,
+    };
+
+    OnchainCrossChainOrder {
+        fill_deadline,
+        order_data_type: go_fast_json_order_data_type(),
+        order_data: to_json_binary(&order_data).unwrap(),
+    }
+}
+
+#[test]
+fn test_opened_orders_are_queryable() {
+    let (mut deps, env) = common::default_instantiate();
+
+    let user = deps.api.addr_make("user");
+    let user_hex = HexBinary::from(left_pad_bytes(bech32_decode(user.as_str()).unwrap(), 32));
+
+    let fill_deadline = env.block.time.seconds() as u32 + 1000;
+
+    let mut opened = vec![];
+    for amount_in in [100_000_000, 200_000_000, 300_000_000] {
+        let order = onchain_order(user_hex.clone(), amount_in, fill_deadline);
+
+        // the gateway nonce is mocked, so orders are told apart by amount
+        let resolved_order = cw_7683::query::resolve(deps.as_ref(), order.clone()).unwrap();
+
+        cw_7683::contract::execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(user.as_str(), &[coin(amount_in, "uosmo")]),
+            ExecuteMsg::Open { order },
+        )
+        .unwrap();
+
+        opened.push(OpenedOrder {
+            order_id: resolved_order.order_id.clone(),
+            user: user.clone(),
+            gateway: Addr::unchecked("go-fast-gateway"),
+            resolved_order,
+        });
+    }
+    opened.sort_by(|a, b| a.order_id.cmp(&b.order_id));
+
+    let order: OpenedOrder = from_json(
+        cw_7683::contract::query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::OrderById {
+                order_id: opened[1].order_id.clone(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(order, opened[1]);
+
+    let orders: Vec<OpenedOrder> = from_json(
+        cw_7683::contract::query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::OrdersByUser {
+                user: user.to_string(),
+                start_after: None,
+                limit: Some(2),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(orders, opened[..2]);
+
+    let orders: Vec<OpenedOrder> = from_json(
+        cw_7683::contract::query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::OrdersByUser {
+                user: user.to_string(),
+                start_after: Some(opened[1].order_id.clone()),
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(orders, opened[2..]);
+
+    let orders: Vec<OpenedOrder> = from_json(
+        cw_7683::contract::query(
+            deps.as_ref(),
+            env,
+            QueryMsg::OrdersByUser {
+                user: deps.api.addr_make("other").to_string(),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+
+    assert!(orders.is_empty());
+}
+
+#[test]
+fn test_order_status_is_proxied_to_gateway() {
+    let (deps, env) = common::default_instantiate();
+
+    let order_id = HexBinary::from(vec![1; 32]);
+
+    let status: OrderStatusResponse = from_json(
+        cw_7683::contract::query(
+            deps.as_ref(),
+            env,
+            QueryMsg::OrderStatus {
+                order_id: order_id.clone(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(status.order_id, order_id);
+    assert_eq!(status.status, OrderStatus::Filled);
+}
@@ -1,13 +1,21 @@
-use cosmwasm_std::{testing::MockApi, to_json_binary, HexBinary, Uint128};
+use cosmwasm_std::{to_json_binary, HexBinary, Uint128};
 use cw_7683::{
-    contract::GO_FAST_ORDER_TYPE,
+    helpers::{go_fast_json_order_data_type, token_to_bytes32},
+    state::DESTINATION_SETTLERS,
     types::{FillInstruction, OnchainCrossChainOrder, OrderData, Output, ResolvedCrossChainOrder},
 };
-use go_fast::helpers::{bech32_decode, left_pad_bytes};
+use go_fast::{
+    helpers::{bech32_decode, keccak256_hash, left_pad_bytes},
+    FastTransferOrder,
+};
+
+pub mod common;
 
 #[test]
 fn test_resolve() {
-    let user_address = MockApi::default().addr_make("user");
+    let (deps, _) = common::default_instantiate();
+
+    let user_address = deps.api.addr_make("user");
     let user_address_hex = HexBinary::from(left_pad_bytes(
         bech32_decode(user_address.as_str()).unwrap(),
         32,
@@ -23,9 +31,8 @@ fn test_resolve() {
         output_token: output_token.to_string(),
         amount_in: Uint128::new(100_000_000),
         amount_out: Uint128::new(98_000_000),
-        source_domain: 2,
-        destination_domain: 1,
-        nonce: 1,
+        destination_domain: Some(1),
+        destination_chain_id: None,
         timeout_timestamp: 1234567890,
         data: None,
     };
@@ -34,38 +41,224 @@ fn test_resolve() {
 
     let onchain_order = OnchainCrossChainOrder {
         fill_deadline: 1234567890,
-        order_data_type: GO_FAST_ORDER_TYPE.to_string(),
+        order_data_type: go_fast_json_order_data_type(),
         order_data: order_data.clone(),
     };
 
+    // nonce and source domain come from the gateway, not the order data
+    let fast_transfer_order = FastTransferOrder {
+        sender: user_address_hex.clone(),
+        recipient: user_address_hex.clone(),
+        amount_in: Uint128::new(100_000_000),
+        amount_out: Uint128::new(98_000_000),
+        nonce: common::GATEWAY_NEXT_NONCE,
+        source_domain: common::GATEWAY_LOCAL_DOMAIN,
+        destination_domain: 1,
+        timeout_timestamp: 1234567890,
+        data: None,
+    };
+
     let expected = ResolvedCrossChainOrder {
         user: user_address_hex.clone(),
-        origin_domain: 2,
-        open_deadline: 18446744073709551615,
+        origin_chain_id: 2,
+        open_deadline: u32::MAX,
         fill_deadline: 1234567890,
+        order_id: fast_transfer_order.id(),
         max_spent: vec![Output {
-            token: "uatom".to_string(),
+            token: left_pad_bytes(b"uatom".to_vec(), 32).into(),
             amount: Uint128::new(98_000_000),
             recipient: user_address_hex.clone(),
-            domain: 1,
+            chain_id: 1,
         }],
         min_received: vec![Output {
-            token: "uosmo".to_string(),
+            token: left_pad_bytes(b"uosmo".to_vec(), 32).into(),
             amount: Uint128::new(100_000_000),
             recipient: user_address_hex,
-            domain: 2,
+            chain_id: 2,
         }],
         fill_instructions: vec![FillInstruction {
-            destination_domain: 1,
-            destination_settler: HexBinary::from_hex(
-                "04f8996da763b7a969b1028ee3007569eaf3a635486ddab211d512c85b9df8fb",
-            )
-            .unwrap(),
-            origin_data: HexBinary::from_hex("7b2273656e646572223a2230346638393936646137363362376139363962313032386565333030373536396561663361363335343836646461623231316435313263383562396466386662222c22726563697069656e74223a2230346638393936646137363362376139363962313032386565333030373536396561663361363335343836646461623231316435313263383562396466386662222c22616d6f756e745f696e223a22313030303030303030222c22616d6f756e745f6f7574223a223938303030303030222c226e6f6e6365223a312c22736f757263655f646f6d61696e223a322c2264657374696e6174696f6e5f646f6d61696e223a312c2274696d656f75745f74696d657374616d70223a313233343536373839302c2264617461223a6e756c6c7d").unwrap().into(),
+            destination_chain_id: 1,
+            destination_settler: HexBinary::from_hex(common::DESTINATION_SETTLER).unwrap(),
+            origin_data: HexBinary::from(fast_transfer_order).into(),
         }],
     };
 
-    let resolved_order = cw_7683::query::resolve(onchain_order).unwrap();
+    let resolved_order = cw_7683::query::resolve(deps.as_ref(), onchain_order).unwrap();
 
     assert_eq!(expected, resolved_order);
 }
+
+#[test]
+fn test_resolve_fails_on_unknown_destination_domain() {
+    let (mut deps, _) = common::default_instantiate();
+
+    let user_address = deps.api.addr_make("user");
+    let user_address_hex = HexBinary::from(left_pad_bytes(
+        bech32_decode(user_address.as_str()).unwrap(),
+        32,
+    ));
+
+    let order = OrderData {
+        sender: user_address_hex.clone(),
+        recipient: user_address_hex,
+        input_token: "uosmo".to_string(),
+        output_token: "uatom".to_string(),
+        amount_in: Uint128::new(100_000_000),
+        amount_out: Uint128::new(98_000_000),
+        destination_domain: Some(3),
+        destination_chain_id: None,
+        timeout_timestamp: 1234567890,
+        data: None,
+    };
+
+    let onchain_order = OnchainCrossChainOrder {
+        fill_deadline: 1234567890,
+        order_data_type: go_fast_json_order_data_type(),
+        order_data: to_json_binary(&order).unwrap(),
+    };
+
+    let err = cw_7683::query::resolve(deps.as_ref(), onchain_order.clone())
+        .unwrap_err()
+        .to_string();
+
+    assert_eq!(err, "Unknown destination domain");
+
+    // a settler alone is not enough, the domain needs a chain ID
+    DESTINATION_SETTLERS
+        .save(deps.as_mut().storage, 3, &HexBinary::from(vec![1; 32]))
+        .unwrap();
+
+    let err = cw_7683::query::resolve(deps.as_ref(), onchain_order)
+        .unwrap_err()
+        .to_string();
+
+    assert_eq!(err, "Unknown domain: 3");
+}
+
+#[test]
+fn test_resolve_fails_on_oversized_recipient() {
+    let (deps, _) = common::default_instantiate();
+
+    let user_address = deps.api.addr_make("user");
+    let user_address_hex = HexBinary::from(left_pad_bytes(
+        bech32_decode(user_address.as_str()).unwrap(),
+        32,
+    ));
+
+    let order = OrderData {
+        sender: user_address_hex,
+        recipient: HexBinary::from(vec![1; 33]),
+        input_token: "uosmo".to_string(),
+        output_token: "uatom".to_string(),
+        amount_in: Uint128::new(100_000_000),
+        amount_out: Uint128::new(98_000_000),
+        destination_domain: Some(common::DESTINATION_DOMAIN),
+        destination_chain_id: None,
+        timeout_timestamp: 1234567890,
+        data: None,
+    };
+
+    let onchain_order = OnchainCrossChainOrder {
+        fill_deadline: 1234567890,
+        order_data_type: go_fast_json_order_data_type(),
+        order_data: to_json_binary(&order).unwrap(),
+    };
+
+    let err = cw_7683::query::resolve(deps.as_ref(), onchain_order)
+        .unwrap_err()
+        .to_string();
+
+    assert_eq!(err, "Invalid order recipient: expected 32 bytes");
+}
+
+#[test]
+fn test_token_to_bytes32() {
+    assert_eq!(
+        token_to_bytes32("0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48"),
+        HexBinary::from_hex("000000000000000000000000a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48")
+            .unwrap()
+    );
+    assert_eq!(
+        token_to_bytes32("uusdc"),
+        HexBinary::from(left_pad_bytes(b"uusdc".to_vec(), 32))
+    );
+    assert_eq!(
+        token_to_bytes32("ibc/498A0751C798A0D9A389AA3691123DADA57DAA4FE165D5C75894505B876BA6E4"),
+        keccak256_hash(b"ibc/498A0751C798A0D9A389AA3691123DADA57DAA4FE165D5C75894505B876BA6E4")
+    );
+}
+
+#[test]
+fn test_resolved_order_abi_encoding() {
+    let word = |byte: u8| HexBinary::from(vec![byte; 32]);
+
+    let resolved_order = ResolvedCrossChainOrder {
+        user: word(0x11),
+        origin_chain_id: 2,
+        open_deadline: u32::MAX,
+        fill_deadline: 1234567890,
+        order_id: word(0x22),
+        max_spent: vec![Output {
+            token: word(0x33),
+            amount: Uint128::new(98_000_000),
+            recipient: word(0x44),
+            chain_id: 1,
+        }],
+        min_received: vec![Output {
+            token: word(0x55),
+            amount: Uint128::new(100_000_000),
+            recipient: word(0x66),
+            chain_id: 2,
+        }],
+        fill_instructions: vec![FillInstruction {
+            destination_chain_id: 1,
+            destination_settler: word(0x77),
+            origin_data: b"abc".to_vec().into(),
+        }],
+    };
+
+    // abi.encode(resolvedOrder) as emitted in the data of the ERC-7683 Open event
+    let expected = HexBinary::from_hex(concat!(
+        // offset of the tuple
+        "0000000000000000000000000000000000000000000000000000000000000020",
+        // user, originChainId, openDeadline, fillDeadline, orderId
+        "1111111111111111111111111111111111111111111111111111111111111111",
+        "0000000000000000000000000000000000000000000000000000000000000002",
+        "00000000000000000000000000000000000000000000000000000000ffffffff",
+        "00000000000000000000000000000000000000000000000000000000499602d2",
+        "2222222222222222222222222222222222222222222222222222222222222222",
+        // offsets of maxSpent, minReceived and fillInstructions
+        "0000000000000000000000000000000000000000000000000000000000000100",
+        "00000000000000000000000000000000000000000000000000000000000001a0",
+        "0000000000000000000000000000000000000000000000000000000000000240",
+        // maxSpent
+        "0000000000000000000000000000000000000000000000000000000000000001",
+        "3333333333333333333333333333333333333333333333333333333333333333",
+        "0000000000000000000000000000000000000000000000000000000005d75c80",
+        "4444444444444444444444444444444444444444444444444444444444444444",
+        "0000000000000000000000000000000000000000000000000000000000000001",
+        // minReceived
+        "0000000000000000000000000000000000000000000000000000000000000001",
+        "5555555555555555555555555555555555555555555555555555555555555555",
+        "0000000000000000000000000000000000000000000000000000000005f5e100",
+        "6666666666666666666666666666666666666666666666666666666666666666",
+        "0000000000000000000000000000000000000000000000000000000000000002",
+        // fillInstructions
+        "0000000000000000000000000000000000000000000000000000000000000001",
+        "0000000000000000000000000000000000000000000000000000000000000020",
+        "0000000000000000000000000000000000000000000000000000000000000001",
+        "7777777777777777777777777777777777777777777777777777777777777777",
+        "0000000000000000000000000000000000000000000000000000000000000060",
+        "0000000000000000000000000000000000000000000000000000000000000003",
+        "6162630000000000000000000000000000000000000000000000000000000000",
+    ))
+    .unwrap();
+
+    assert_eq!(resolved_order.abi_encode().unwrap(), expected);
+
+    let oversized_order = ResolvedCrossChainOrder {
+        user: HexBinary::from(vec![0x11; 33]),
+        ..resolved_order
+    };
+    assert!(oversized_order.abi_encode().is_err());
+}
@@ -1,5 +1,5 @@
 use cosmwasm_std::{
-    entry_point, to_json_binary, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response,
+    entry_point, to_json_binary, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Reply, Response,
     StdResult,
 };
 use cw2::set_contract_version;
@@ -7,21 +7,47 @@ use cw2::set_contract_version;
 use crate::{
     error::{ContractError, ContractResponse},
     execute::{
-        add_remote_domain, fill_order, handle, initiate_settlement, initiate_timeout, submit_order,
-        update_config,
+        add_allowed_submitter, add_remote_domain, cancel_proposal, claim_refunds, execute_proposal,
+        execute_queued_settlement, fill_order, grant_role, handle, ibc_hook_submit_order,
+        ibc_lifecycle_complete, ibc_transfer_reply, initiate_settlement, initiate_timeout, pause,
+        propose_action, receive_mock_command, reclaim_orders, remove_allowed_submitter,
+        revoke_role, set_remote_domain_enabled, submit_order, sunset_remote_domain, unpause,
+        update_ownership, veto_queued_settlement,
     },
     query::{
-        get_config, get_local_domain, get_order_fill, get_remote_domain, get_remote_domains,
-        order_fills_by_filler, quote_initiate_settlement,
+        get_allowed_submitters, get_claimable_refunds, get_config, get_domain_sunsets,
+        get_interchain_security_module, get_ism_module_type, get_local_domain, get_next_nonce,
+        get_order_fill, get_order_status, get_paused, get_pending_attestations, get_proposal,
+        get_proposals, get_queued_settlement, get_queued_settlements, get_remote_domain,
+        get_remote_domains, get_roles, get_rotation_grace_period, get_timelock_delay,
+        order_fills_by_filler, quote_initiate_settlement, route_message, verify_message,
     },
-    state::{CONFIG, LOCAL_DOMAIN, NONCE},
+    state::{CONFIG, LOCAL_DOMAIN, NONCE, TIMELOCK_DELAY},
 };
-use go_fast::gateway::{Config, ExecuteMsg, InstantiateMsg, QueryMsg};
+use go_fast::gateway::{Config, ExecuteMsg, InstantiateMsg, QueryMsg, SudoMsg};
+use hyperlane::ism::{IsmQueryMsg, IsmSpecifierQueryMsg, RoutingIsmQueryMsg};
 
 // version info for migration info
 const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+pub const IBC_TRANSFER_REPLY_ID: u64 = 1;
+
+pub const IBC_REFUND_TIMEOUT_SECONDS: u64 = 60 * 60;
+
+pub const IBC_SETTLEMENT_TIMEOUT_SECONDS: u64 = 24 * 60 * 60;
+
+/// Version negotiated on the channels gateways exchange commands over.
+pub const IBC_APP_VERSION: &str = "go-fast-gateway-1";
+
+pub const DEFAULT_TIMELOCK_DELAY_SECONDS: u64 = 24 * 60 * 60;
+
+pub const DEFAULT_ROTATION_GRACE_PERIOD_SECONDS: u64 = 7 * 24 * 60 * 60;
+
+/// Time after a domain sunset during which late settlements from it can still
+/// land before its orders become reclaimable.
+pub const SUNSET_RECLAIM_DELAY_SECONDS: u64 = 7 * 24 * 60 * 60;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(_deps: DepsMut, _env: Env, _msg: Empty) -> Result<Response, ContractError> {
     // No state migrations performed, just returned a Response
@@ -51,6 +77,8 @@ pub fn instantiate(
 
     NONCE.save(deps.storage, &0)?;
 
+    TIMELOCK_DELAY.save(deps.storage, &msg.timelock_delay_seconds)?;
+
     Ok(Response::default())
 }
 
@@ -61,12 +89,31 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> C
         ExecuteMsg::InitiateSettlement {
             order_ids,
             repayment_address,
-        } => initiate_settlement(deps, info, order_ids, repayment_address),
+        } => initiate_settlement(deps, env, info, order_ids, repayment_address),
         ExecuteMsg::InitiateTimeout { orders } => initiate_timeout(deps, env, info, orders),
-        ExecuteMsg::UpdateConfig { config } => update_config(deps, info, config),
+        ExecuteMsg::ProposeAction { action } => propose_action(deps, env, info, action),
+        ExecuteMsg::ExecuteProposal { id } => execute_proposal(deps, env, info, id),
+        ExecuteMsg::CancelProposal { id } => cancel_proposal(deps, info, id),
         ExecuteMsg::AddRemoteDomain { domain, address } => {
             add_remote_domain(deps, info, domain, address)
         }
+        ExecuteMsg::SetRemoteDomainEnabled { domain, enabled } => {
+            set_remote_domain_enabled(deps, info, domain, enabled)
+        }
+        ExecuteMsg::SunsetRemoteDomain { domain } => sunset_remote_domain(deps, env, info, domain),
+        ExecuteMsg::ReclaimOrders { orders } => reclaim_orders(deps, env, orders),
+        ExecuteMsg::ClaimRefunds { recipient } => claim_refunds(deps, info, recipient),
+        ExecuteMsg::ExecuteQueuedSettlement { id } => execute_queued_settlement(deps, env, id),
+        ExecuteMsg::VetoQueuedSettlement { id } => veto_queued_settlement(deps, info, id),
+        ExecuteMsg::AddAllowedSubmitter { address } => add_allowed_submitter(deps, info, address),
+        ExecuteMsg::RemoveAllowedSubmitter { address } => {
+            remove_allowed_submitter(deps, info, address)
+        }
+        ExecuteMsg::GrantRole { role, address } => grant_role(deps, info, role, address),
+        ExecuteMsg::RevokeRole { role, address } => revoke_role(deps, info, role, address),
+        ExecuteMsg::Pause {} => pause(deps, info),
+        ExecuteMsg::Unpause {} => unpause(deps, info),
+        ExecuteMsg::UpdateOwnership(action) => update_ownership(deps, env, info, action),
         ExecuteMsg::SubmitOrder {
             sender,
             recipient,
@@ -75,6 +122,7 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> C
             destination_domain,
             timeout_timestamp,
             data,
+            refund_address,
         } => submit_order(
             deps,
             info,
@@ -85,19 +133,85 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> C
             destination_domain,
             timeout_timestamp,
             data,
+            refund_address,
+            None,
         ),
-        ExecuteMsg::Handle(handle_msg) => handle(deps, info, handle_msg),
+        ExecuteMsg::IbcHookSubmitOrder {
+            ibc_origin,
+            recipient,
+            amount_in,
+            amount_out,
+            destination_domain,
+            timeout_timestamp,
+            data,
+        } => ibc_hook_submit_order(
+            deps,
+            info,
+            ibc_origin,
+            recipient,
+            amount_in,
+            amount_out,
+            destination_domain,
+            timeout_timestamp,
+            data,
+        ),
+        ExecuteMsg::Handle(handle_msg) => handle(deps, env, info, handle_msg),
+        ExecuteMsg::ReceiveCommand {
+            origin,
+            sender,
+            body,
+        } => receive_mock_command(deps, env, info, origin, sender, body),
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> ContractResponse {
+    match msg.id {
+        IBC_TRANSFER_REPLY_ID => ibc_transfer_reply(deps, msg),
+        id => Err(ContractError::UnknownReplyId(id)),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn sudo(deps: DepsMut, _env: Env, msg: SudoMsg) -> ContractResponse {
+    match msg {
+        SudoMsg::IbcLifecycleComplete(msg) => ibc_lifecycle_complete(deps, msg),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Config {} => to_json_binary(&get_config(deps)?),
         QueryMsg::LocalDomain {} => to_json_binary(&get_local_domain(deps)?),
+        QueryMsg::NextNonce {} => to_json_binary(&get_next_nonce(deps)?),
         QueryMsg::RemoteDomain { domain } => to_json_binary(&get_remote_domain(deps, domain)?),
         QueryMsg::RemoteDomains {} => to_json_binary(&get_remote_domains(deps)?),
+        QueryMsg::RotationGracePeriod {} => to_json_binary(&get_rotation_grace_period(deps)?),
+        QueryMsg::DomainSunsets {} => to_json_binary(&get_domain_sunsets(deps)?),
+        QueryMsg::ClaimableRefunds { address } => {
+            to_json_binary(&get_claimable_refunds(deps, address)?)
+        }
+        QueryMsg::QueuedSettlement { id } => to_json_binary(&get_queued_settlement(deps, id)?),
+        QueryMsg::QueuedSettlements { start_after, limit } => {
+            to_json_binary(&get_queued_settlements(deps, start_after, limit)?)
+        }
+        QueryMsg::PendingAttestations {
+            domain,
+            start_after,
+            limit,
+        } => to_json_binary(&get_pending_attestations(deps, domain, start_after, limit)?),
+        QueryMsg::AllowedSubmitters {} => to_json_binary(&get_allowed_submitters(deps)?),
+        QueryMsg::Roles { role } => to_json_binary(&get_roles(deps, role)?),
+        QueryMsg::Paused {} => to_json_binary(&get_paused(deps)?),
+        QueryMsg::TimelockDelay {} => to_json_binary(&get_timelock_delay(deps)?),
+        QueryMsg::Proposal { id } => to_json_binary(&get_proposal(deps, id)?),
+        QueryMsg::Proposals { start_after, limit } => {
+            to_json_binary(&get_proposals(deps, start_after, limit)?)
+        }
+        QueryMsg::Ownership {} => to_json_binary(&cw_ownable::get_ownership(deps.storage)?),
         QueryMsg::OrderFill { order_id } => to_json_binary(&get_order_fill(deps, order_id)?),
+        QueryMsg::OrderStatus { order_id } => to_json_binary(&get_order_status(deps, order_id)?),
         QueryMsg::QuoteInitiateSettlement {
             order_ids,
             repayment_address,
@@ -113,5 +227,15 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             start_after,
             limit,
         } => to_json_binary(&order_fills_by_filler(deps, filler, start_after, limit)?),
+        QueryMsg::IsmSpecifier(IsmSpecifierQueryMsg::InterchainSecurityModule()) => {
+            to_json_binary(&get_interchain_security_module(deps, env)?)
+        }
+        QueryMsg::Ism(IsmQueryMsg::ModuleType {}) => to_json_binary(&get_ism_module_type()),
+        QueryMsg::Ism(IsmQueryMsg::Verify { metadata, message }) => {
+            to_json_binary(&verify_message(deps, metadata, message)?)
+        }
+        QueryMsg::RoutingIsm(RoutingIsmQueryMsg::Route { message }) => {
+            to_json_binary(&route_message(deps, message)?)
+        }
     }
 }
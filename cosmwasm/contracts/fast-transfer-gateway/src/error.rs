@@ -33,6 +33,27 @@ pub enum ContractError {
     #[error("Unknown remote domain")]
     UnknownRemoteDomain,
 
+    #[error("Invalid config: {0}")]
+    InvalidConfig(String),
+
+    #[error("Remote domain is disabled")]
+    RemoteDomainDisabled,
+
+    #[error("Destination domain has not been sunset")]
+    DomainNotSunset,
+
+    #[error("No refunds to claim")]
+    NoClaimableRefunds,
+
+    #[error("Remote domain has been sunset")]
+    RemoteDomainSunset,
+
+    #[error("Order was submitted after the destination domain was sunset")]
+    OrderSubmittedAfterSunset,
+
+    #[error("Orders for the sunset domain are reclaimable from {0}")]
+    SunsetReclaimNotAvailable(u64),
+
     #[error("Source domains must match")]
     SourceDomainsMustMatch,
 
@@ -53,6 +74,60 @@ pub enum ContractError {
 
     #[error("Invalid repayment address")]
     InvalidRepaymentAddress,
+
+    #[error("Order sender does not match caller")]
+    InvalidOrderSender,
+
+    #[error("Invalid IBC memo")]
+    InvalidIbcMemo,
+
+    #[error("Caller does not have the {0} role")]
+    MissingRole(&'static str),
+
+    #[error("Contract is paused")]
+    Paused,
+
+    #[error("Proposal {0} not found")]
+    ProposalNotFound(u64),
+
+    #[error("Proposal not executable until {0}")]
+    ProposalNotExecutable(u64),
+
+    #[error("Changing a remote domain address requires a timelock proposal")]
+    RemoteDomainChangeRequiresProposal,
+
+    #[error("Queued settlement {0} not found")]
+    QueuedSettlementNotFound(u64),
+
+    #[error("Queued settlement not executable until {0}")]
+    QueuedSettlementNotExecutable(u64),
+
+    #[error("Attestation transport must differ from the domain transport")]
+    AttestationTransportNotIndependent,
+
+    #[error("Settlement of the order fill is already pending")]
+    FillSettlementPending,
+
+    #[error("Order fill already settled")]
+    FillAlreadySettled,
+
+    #[error("Gateway channels must be unordered")]
+    InvalidIbcChannelOrder,
+
+    #[error("Invalid IBC channel version: {0}")]
+    InvalidIbcVersion(String),
+
+    #[error("No remote domain settles over channel {0}")]
+    UnknownIbcChannel(String),
+
+    #[error("Channel {0} already carries the commands of domain {1}")]
+    IbcChannelInUse(String, u32),
+
+    #[error("Unknown reply id: {0}")]
+    UnknownReplyId(u64),
+
+    #[error("IBC transfer timeout is out of range")]
+    InvalidIbcTransferTimeout,
 }
 
 pub type ContractResult<T> = Result<T, ContractError>;
@@ -1,45 +1,557 @@
 use cosmwasm_std::{
-    coin, to_json_binary, Addr, BankMsg, Binary, CosmosMsg, DepsMut, Env, HexBinary, MessageInfo,
-    Response, Uint128, WasmMsg,
+    coin, from_json, Addr, BankMsg, Binary, DepsMut, Empty, Env, Event, HexBinary, MessageInfo,
+    Order, Reply, Response, StdError, StdResult, Storage, SubMsg, Uint128, WasmMsg,
 };
 use cw_ownable::assert_owner;
-use go_fast::{gateway::Config, FastTransferOrder};
-use hyperlane::mailbox::{DispatchMsg, ExecuteMsg as MailboxExecuteMsg};
+use go_fast::{
+    gateway::{
+        Config, ConfigUpdate, DomainSunset, FillSettlementStatus, IbcLifecycleComplete, IbcOrigin,
+        Proposal, QueuedSettlement, RemoteAddressRotation, Role, TimelockedAction, Transport,
+    },
+    receiver::{FillAction, GoFastReceiveMsg},
+    FastTransferOrder,
+};
 
 use crate::{
-    error::{ContractError, ContractResponse},
+    contract::{IBC_REFUND_TIMEOUT_SECONDS, IBC_TRANSFER_REPLY_ID, SUNSET_RECLAIM_DELAY_SECONDS},
+    error::{ContractError, ContractResponse, ContractResult},
     helpers::{
-        assert_correct_funds, assert_local_domain, assert_order_is_expired,
-        assert_order_is_not_expired, assert_order_not_filled, assert_remote_domain, bech32_encode,
-        get_order_settlement_details,
+        assert_can_propose, assert_correct_funds, assert_ibc_channel_available,
+        assert_independent_transports, assert_local_domain, assert_not_paused,
+        assert_order_is_expired, assert_order_is_not_expired, assert_order_not_filled,
+        assert_order_sender, assert_remote_domain, assert_role, bech32_decode, bech32_encode,
+        derive_intermediate_sender, get_order_settlement_details, left_pad_bytes, validate_config,
+    },
+    msg::{
+        IbcTransferFallback, OrderStatus, SettleOrdersMessage, SettlementDetails, SettlementWindow,
+        TimeoutOrdersMessage,
     },
-    msg::{Command, OrderStatus, SettleOrdersMessage, SettlementDetails, TimeoutOrdersMessage},
     state::{
-        self, next_nonce, CONFIG, LOCAL_DOMAIN, ORDER_STATUSES, REMOTE_DOMAINS, SETTLEMENT_DETAILS,
+        self, next_nonce, ALLOWED_SUBMITTERS, CLAIMABLE_REFUNDS, CONFIG, DISABLED_REMOTE_DOMAINS,
+        DOMAIN_SUNSETS, IBC_TRANSFERS, INTERCHAIN_SECURITY_MODULE, LOCAL_DOMAIN, NONCE,
+        ORDER_STATUSES, PAUSED, PENDING_ATTESTATIONS, PENDING_IBC_TRANSFERS, PROPOSALS,
+        QUEUED_SETTLEMENTS, REMOTE_DOMAINS, REMOTE_DOMAIN_ATTESTATIONS, REMOTE_DOMAIN_ISMS,
+        REMOTE_DOMAIN_ROTATIONS, REMOTE_DOMAIN_TRANSPORTS, REMOVED_REMOTE_DOMAINS, ROLES,
+        ROTATION_GRACE_PERIOD, SETTLEMENT_DELAYS, SETTLEMENT_DETAILS, SETTLEMENT_WINDOWS,
+        TIMELOCK_DELAY,
     },
+    transfer::{decode_msg_transfer_response, memo_with_callback, MsgTransfer},
+    transport::{dispatch_attested_command, receive_command, CommandStatus},
 };
 
-pub fn update_config(deps: DepsMut, info: MessageInfo, config: Config) -> ContractResponse {
-    assert_owner(deps.storage, &info.sender)?;
+pub fn add_remote_domain(
+    deps: DepsMut,
+    info: MessageInfo,
+    domain: u32,
+    address: HexBinary,
+) -> ContractResponse {
+    assert_role(deps.as_ref(), &info.sender, Role::DomainManager)?;
+
+    // Repointing a known domain lets the new address settle orders locked
+    // against it, so that has to go through the timelock. The same goes for
+    // adding back a removed domain.
+    if let Some(current) = REMOTE_DOMAINS.may_load(deps.storage, domain)? {
+        if current != address {
+            return Err(ContractError::RemoteDomainChangeRequiresProposal);
+        }
+    }
 
-    CONFIG.save(deps.storage, &config)?;
+    if REMOVED_REMOTE_DOMAINS.has(deps.storage, domain) {
+        return Err(ContractError::RemoteDomainChangeRequiresProposal);
+    }
+
+    REMOTE_DOMAINS.save(deps.storage, domain, &address)?;
 
     Ok(Response::default())
 }
 
-pub fn add_remote_domain(
+pub fn set_remote_domain_enabled(
     deps: DepsMut,
     info: MessageInfo,
     domain: u32,
+    enabled: bool,
+) -> ContractResponse {
+    assert_role(deps.as_ref(), &info.sender, Role::DomainManager)?;
+
+    if !REMOTE_DOMAINS.has(deps.storage, domain) {
+        return Err(ContractError::UnknownRemoteDomain);
+    }
+
+    // Orders to a sunset domain can be reclaimed locally, so it must not
+    // accept new ones that could also be filled there.
+    if enabled && DOMAIN_SUNSETS.has(deps.storage, domain) {
+        return Err(ContractError::RemoteDomainSunset);
+    }
+
+    if enabled {
+        DISABLED_REMOTE_DOMAINS.remove(deps.storage, domain);
+    } else {
+        DISABLED_REMOTE_DOMAINS.save(deps.storage, domain, &Empty {})?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "set_remote_domain_enabled")
+        .add_attribute("domain", domain.to_string())
+        .add_attribute("enabled", enabled.to_string()))
+}
+
+fn remove_remote_domain(storage: &mut dyn Storage, domain: u32) -> ContractResult<()> {
+    if !REMOTE_DOMAINS.has(storage, domain) {
+        return Err(ContractError::UnknownRemoteDomain);
+    }
+
+    REMOTE_DOMAINS.remove(storage, domain);
+    REMOVED_REMOTE_DOMAINS.save(storage, domain, &Empty {})?;
+    DISABLED_REMOTE_DOMAINS.remove(storage, domain);
+    REMOTE_DOMAIN_ROTATIONS.remove(storage, domain);
+    let transport = REMOTE_DOMAIN_TRANSPORTS.may_load(storage, domain)?;
+    let attestation = REMOTE_DOMAIN_ATTESTATIONS.may_load(storage, domain)?;
+    state::update_ibc_channel_domain(storage, domain, transport.as_ref(), None)?;
+    state::update_ibc_channel_domain(
+        storage,
+        domain,
+        attestation
+            .as_ref()
+            .map(|attestation| &attestation.transport),
+        None,
+    )?;
+
+    REMOTE_DOMAIN_TRANSPORTS.remove(storage, domain);
+    REMOTE_DOMAIN_ATTESTATIONS.remove(storage, domain);
+    SETTLEMENT_DELAYS.remove(storage, domain);
+    SETTLEMENT_WINDOWS.remove(storage, domain);
+    REMOTE_DOMAIN_ISMS.remove(storage, domain);
+    remove_pending_attestations(storage, domain)?;
+
+    Ok(())
+}
+
+fn remove_pending_attestations(storage: &mut dyn Storage, domain: u32) -> ContractResult<()> {
+    let payload_hashes = PENDING_ATTESTATIONS
+        .prefix(domain)
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    for payload_hash in payload_hashes {
+        PENDING_ATTESTATIONS.remove(storage, (domain, payload_hash));
+    }
+
+    Ok(())
+}
+
+/// Points `domain` at a new router. The previous router stays accepted for
+/// the rotation grace period so settlements it already dispatched can land.
+fn rotate_remote_domain(
+    storage: &mut dyn Storage,
+    env: &Env,
+    domain: u32,
     address: HexBinary,
+) -> ContractResult<()> {
+    if let Some(previous_address) = REMOTE_DOMAINS.may_load(storage, domain)? {
+        if previous_address != address {
+            let expires_at = env
+                .block
+                .time
+                .plus_seconds(state::rotation_grace_period(storage)?);
+
+            REMOTE_DOMAIN_ROTATIONS.save(
+                storage,
+                domain,
+                &RemoteAddressRotation {
+                    previous_address,
+                    expires_at,
+                },
+            )?;
+        }
+    }
+
+    REMOTE_DOMAINS.save(storage, domain, &address)?;
+
+    Ok(())
+}
+
+/// Retires `domain` as a destination. It stops accepting new orders right
+/// away, and orders still open against it can be reclaimed locally once
+/// `SUNSET_RECLAIM_DELAY_SECONDS` has passed.
+pub fn sunset_remote_domain(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    domain: u32,
+) -> ContractResponse {
+    assert_role(deps.as_ref(), &info.sender, Role::DomainManager)?;
+
+    let sunset = DomainSunset {
+        domain,
+        sunset_at: env.block.time,
+        reclaimable_at: env.block.time.plus_seconds(SUNSET_RECLAIM_DELAY_SECONDS),
+        next_nonce: NONCE.load(deps.storage)? + 1,
+    };
+
+    DOMAIN_SUNSETS.save(deps.storage, domain, &sunset)?;
+
+    if REMOTE_DOMAINS.has(deps.storage, domain) {
+        DISABLED_REMOTE_DOMAINS.save(deps.storage, domain, &Empty {})?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "sunset_remote_domain")
+        .add_attribute("domain", domain.to_string())
+        .add_attribute(
+            "reclaimable_at",
+            sunset.reclaimable_at.seconds().to_string(),
+        ))
+}
+
+/// Refunds timed out orders sent to a sunset domain without waiting for a
+/// timeout message from it.
+pub fn reclaim_orders(deps: DepsMut, env: Env, orders: Vec<FastTransferOrder>) -> ContractResponse {
+    let config = CONFIG.load(deps.storage)?;
+
+    let mut attrs = Vec::new();
+    let mut msgs = Vec::new();
+
+    for order in orders {
+        assert_order_is_expired(&env, &order)?;
+
+        let sunset = DOMAIN_SUNSETS
+            .may_load(deps.storage, order.destination_domain)?
+            .ok_or(ContractError::DomainNotSunset)?;
+
+        if env.block.time < sunset.reclaimable_at {
+            return Err(ContractError::SunsetReclaimNotAvailable(
+                sunset.reclaimable_at.seconds(),
+            ));
+        }
+
+        if order.nonce >= sunset.next_nonce {
+            return Err(ContractError::OrderSubmittedAfterSunset);
+        }
+
+        let order_id = order.id();
+        let order_settlement_details = get_order_settlement_details(deps.storage, &order_id)?;
+
+        let status = ORDER_STATUSES
+            .may_load(deps.storage, order_id.to_vec())?
+            .unwrap_or_default();
+
+        if status != OrderStatus::Unfilled {
+            continue;
+        }
+
+        msgs.push(refund_order_msg(
+            deps.storage,
+            &env,
+            &config,
+            &order_id,
+            order_settlement_details,
+        )?);
+        attrs.push(("action".to_string(), "order_reclaimed".to_string()));
+        attrs.push(("order_id".to_string(), order_id.to_string()));
+        ORDER_STATUSES.save(deps.storage, order_id.to_vec(), &OrderStatus::Refunded)?;
+    }
+
+    Ok(Response::new().add_submessages(msgs).add_attributes(attrs))
+}
+
+pub fn propose_action(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    action: TimelockedAction,
+) -> ContractResponse {
+    assert_can_propose(deps.as_ref(), &info.sender, &action)?;
+    assert_independent_transports(deps.storage, &action)?;
+    assert_ibc_channel_available(deps.storage, &action)?;
+
+    // Reject invalid configs up front, they are validated again on execution
+    // as the mailbox may have changed in the meantime.
+    if let TimelockedAction::UpdateConfig(update) = &action {
+        let config = update.clone().apply(CONFIG.load(deps.storage)?);
+        validate_config(deps.as_ref(), &config)?;
+    }
+
+    if let TimelockedAction::RemoveRemoteDomain { domain } = &action {
+        if !REMOTE_DOMAINS.has(deps.storage, *domain) {
+            return Err(ContractError::UnknownRemoteDomain);
+        }
+    }
+
+    if let TimelockedAction::SetInterchainSecurityModule { ism: Some(ism) }
+    | TimelockedAction::SetRemoteDomainIsm { ism: Some(ism), .. } = &action
+    {
+        deps.api.addr_validate(ism)?;
+    }
+
+    let id = state::next_proposal_id(deps.storage)?;
+    let executable_at = env
+        .block
+        .time
+        .plus_seconds(state::timelock_delay(deps.storage)?);
+
+    PROPOSALS.save(
+        deps.storage,
+        id,
+        &Proposal {
+            id,
+            action,
+            proposer: info.sender,
+            executable_at,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "propose_action")
+        .add_attribute("proposal_id", id.to_string())
+        .add_attribute("executable_at", executable_at.seconds().to_string()))
+}
+
+pub fn execute_proposal(deps: DepsMut, env: Env, info: MessageInfo, id: u64) -> ContractResponse {
+    let proposal = PROPOSALS
+        .may_load(deps.storage, id)?
+        .ok_or(ContractError::ProposalNotFound(id))?;
+
+    assert_can_propose(deps.as_ref(), &info.sender, &proposal.action)?;
+
+    if env.block.time < proposal.executable_at {
+        return Err(ContractError::ProposalNotExecutable(
+            proposal.executable_at.seconds(),
+        ));
+    }
+
+    // The other transport of the domain may have changed since the proposal.
+    assert_independent_transports(deps.storage, &proposal.action)?;
+    assert_ibc_channel_available(deps.storage, &proposal.action)?;
+
+    PROPOSALS.remove(deps.storage, id);
+
+    let mut response = Response::new()
+        .add_attribute("action", "execute_proposal")
+        .add_attribute("proposal_id", id.to_string());
+
+    match proposal.action {
+        TimelockedAction::UpdateConfig(update) => {
+            response = response.add_event(update_config(deps, update)?)
+        }
+        TimelockedAction::SetRemoteDomain { domain, address } => {
+            REMOVED_REMOTE_DOMAINS.remove(deps.storage, domain);
+            rotate_remote_domain(deps.storage, &env, domain, address)?
+        }
+        TimelockedAction::RemoveRemoteDomain { domain } => {
+            remove_remote_domain(deps.storage, domain)?
+        }
+        TimelockedAction::UpdateTimelockDelay { delay_seconds } => {
+            TIMELOCK_DELAY.save(deps.storage, &delay_seconds)?
+        }
+        TimelockedAction::UpdateRotationGracePeriod {
+            grace_period_seconds,
+        } => ROTATION_GRACE_PERIOD.save(deps.storage, &grace_period_seconds)?,
+        TimelockedAction::SetRemoteDomainTransport { domain, transport } => {
+            let previous = REMOTE_DOMAIN_TRANSPORTS.may_load(deps.storage, domain)?;
+            state::update_ibc_channel_domain(
+                deps.storage,
+                domain,
+                previous.as_ref(),
+                Some(&transport),
+            )?;
+            REMOTE_DOMAIN_TRANSPORTS.save(deps.storage, domain, &transport)?
+        }
+        TimelockedAction::SetSettlementDelay {
+            domain,
+            settlement_delay,
+        } => match settlement_delay {
+            Some(settlement_delay) => {
+                SETTLEMENT_DELAYS.save(deps.storage, domain, &settlement_delay)?
+            }
+            None => SETTLEMENT_DELAYS.remove(deps.storage, domain),
+        },
+        TimelockedAction::SetInterchainSecurityModule { ism } => match ism {
+            Some(ism) => {
+                INTERCHAIN_SECURITY_MODULE.save(deps.storage, &deps.api.addr_validate(&ism)?)?
+            }
+            None => INTERCHAIN_SECURITY_MODULE.remove(deps.storage),
+        },
+        TimelockedAction::SetRemoteDomainIsm { domain, ism } => match ism {
+            Some(ism) => {
+                REMOTE_DOMAIN_ISMS.save(deps.storage, domain, &deps.api.addr_validate(&ism)?)?
+            }
+            None => REMOTE_DOMAIN_ISMS.remove(deps.storage, domain),
+        },
+        TimelockedAction::SetRemoteDomainAttestation {
+            domain,
+            attestation,
+        } => {
+            let previous = REMOTE_DOMAIN_ATTESTATIONS.may_load(deps.storage, domain)?;
+            state::update_ibc_channel_domain(
+                deps.storage,
+                domain,
+                previous.as_ref().map(|previous| &previous.transport),
+                attestation
+                    .as_ref()
+                    .map(|attestation| &attestation.transport),
+            )?;
+
+            match attestation {
+                Some(attestation) => {
+                    REMOTE_DOMAIN_ATTESTATIONS.save(deps.storage, domain, &attestation)?
+                }
+                None => {
+                    REMOTE_DOMAIN_ATTESTATIONS.remove(deps.storage, domain);
+                    remove_pending_attestations(deps.storage, domain)?;
+                }
+            }
+        }
+    }
+
+    Ok(response)
+}
+
+/// Applies `update` to the stored config and returns an event listing the old
+/// and new value of every field that changed.
+fn update_config(deps: DepsMut, update: ConfigUpdate) -> ContractResult<Event> {
+    let old_config = CONFIG.load(deps.storage)?;
+    let new_config = update.apply(old_config.clone());
+
+    validate_config(deps.as_ref(), &new_config)?;
+
+    CONFIG.save(deps.storage, &new_config)?;
+
+    let fields = [
+        (
+            "token_denom",
+            &old_config.token_denom,
+            &new_config.token_denom,
+        ),
+        (
+            "address_prefix",
+            &old_config.address_prefix,
+            &new_config.address_prefix,
+        ),
+        (
+            "mailbox_addr",
+            &old_config.mailbox_addr,
+            &new_config.mailbox_addr,
+        ),
+        ("hook_addr", &old_config.hook_addr, &new_config.hook_addr),
+    ];
+
+    let event = fields.into_iter().filter(|(_, old, new)| old != new).fold(
+        Event::new("update_config"),
+        |event, (field, old, new)| {
+            event
+                .add_attribute(format!("old_{field}"), old)
+                .add_attribute(format!("new_{field}"), new)
+        },
+    );
+
+    Ok(event)
+}
+
+pub fn cancel_proposal(deps: DepsMut, info: MessageInfo, id: u64) -> ContractResponse {
+    assert_role(deps.as_ref(), &info.sender, Role::Guardian)?;
+
+    if !PROPOSALS.has(deps.storage, id) {
+        return Err(ContractError::ProposalNotFound(id));
+    }
+
+    PROPOSALS.remove(deps.storage, id);
+
+    Ok(Response::new()
+        .add_attribute("action", "cancel_proposal")
+        .add_attribute("proposal_id", id.to_string()))
+}
+
+pub fn add_allowed_submitter(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
 ) -> ContractResponse {
     assert_owner(deps.storage, &info.sender)?;
 
-    REMOTE_DOMAINS.save(deps.storage, domain, &address)?;
+    let address = deps.api.addr_validate(&address)?;
+    ALLOWED_SUBMITTERS.save(deps.storage, address, &Empty {})?;
 
     Ok(Response::default())
 }
 
+pub fn remove_allowed_submitter(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: String,
+) -> ContractResponse {
+    assert_owner(deps.storage, &info.sender)?;
+
+    let address = deps.api.addr_validate(&address)?;
+    ALLOWED_SUBMITTERS.remove(deps.storage, address);
+
+    Ok(Response::default())
+}
+
+pub fn update_ownership(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    action: cw_ownable::Action,
+) -> ContractResponse {
+    let ownership = cw_ownable::update_ownership(deps, &env.block, &info.sender, action)?;
+
+    Ok(Response::new().add_attributes(ownership.into_attributes()))
+}
+
+pub fn grant_role(
+    deps: DepsMut,
+    info: MessageInfo,
+    role: Role,
+    address: String,
+) -> ContractResponse {
+    assert_owner(deps.storage, &info.sender)?;
+
+    let address = deps.api.addr_validate(&address)?;
+    ROLES.save(deps.storage, (role.as_str(), &address), &role)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "grant_role")
+        .add_attribute("role", role.as_str())
+        .add_attribute("address", address))
+}
+
+pub fn revoke_role(
+    deps: DepsMut,
+    info: MessageInfo,
+    role: Role,
+    address: String,
+) -> ContractResponse {
+    assert_owner(deps.storage, &info.sender)?;
+
+    let address = deps.api.addr_validate(&address)?;
+    ROLES.remove(deps.storage, (role.as_str(), &address));
+
+    Ok(Response::new()
+        .add_attribute("action", "revoke_role")
+        .add_attribute("role", role.as_str())
+        .add_attribute("address", address))
+}
+
+/// Stops order submission and fills. Settlements, timeouts and refunds keep
+/// working so funds already in flight can still be released.
+pub fn pause(deps: DepsMut, info: MessageInfo) -> ContractResponse {
+    assert_role(deps.as_ref(), &info.sender, Role::Guardian)?;
+
+    PAUSED.save(deps.storage, &true)?;
+
+    Ok(Response::new().add_attribute("action", "pause"))
+}
+
+/// Only the owner can lift a pause, so a guardian key can halt the gateway
+/// but never resume it.
+pub fn unpause(deps: DepsMut, info: MessageInfo) -> ContractResponse {
+    assert_owner(deps.storage, &info.sender)?;
+
+    PAUSED.save(deps.storage, &false)?;
+
+    Ok(Response::new().add_attribute("action", "unpause"))
+}
+
 pub fn fill_order(
     deps: DepsMut,
     env: Env,
@@ -47,6 +559,8 @@ pub fn fill_order(
     filler: Addr,
     order: FastTransferOrder,
 ) -> ContractResponse {
+    assert_not_paused(deps.as_ref())?;
+
     let config = CONFIG.load(deps.storage)?;
 
     assert_order_is_not_expired(&env, &order)?;
@@ -65,33 +579,153 @@ pub fn fill_order(
         return Err(ContractError::OrderRecipientCannotBeMailbox);
     }
 
-    let msg: CosmosMsg = match order.data {
-        Some(data) => WasmMsg::Execute {
-            contract_addr: recipient_address.clone().to_string(),
-            msg: Binary::from(data),
-            funds: info.funds,
-        }
-        .into(),
-        None => BankMsg::Send {
+    let msg: SubMsg = match order.data {
+        Some(data) => match from_json::<FillAction>(&data) {
+            Ok(FillAction::GoFastReceive { payload }) => SubMsg::new(WasmMsg::Execute {
+                contract_addr: recipient_address.into(),
+                msg: GoFastReceiveMsg {
+                    order_id: order_id.clone(),
+                    sender: order.sender,
+                    source_domain: order.source_domain,
+                    amount: order.amount_out,
+                    denom: config.token_denom,
+                    payload,
+                }
+                .into_json_binary()?,
+                funds: info.funds,
+            }),
+            Ok(FillAction::IbcTransfer {
+                channel_id,
+                receiver,
+                timeout_seconds,
+                memo,
+            }) => {
+                let amount = coin(order.amount_out.u128(), config.token_denom);
+
+                PENDING_IBC_TRANSFERS.push_back(
+                    deps.storage,
+                    &IbcTransferFallback {
+                        order_id: order_id.clone(),
+                        channel_id: channel_id.clone(),
+                        fallback_address: recipient_address,
+                        amount: amount.clone(),
+                        claimable: false,
+                    },
+                )?;
+
+                let transfer = MsgTransfer {
+                    source_port: "transfer".to_string(),
+                    source_channel: channel_id,
+                    token: amount,
+                    sender: env.contract.address.to_string(),
+                    receiver,
+                    timeout_timestamp: timeout_seconds
+                        .checked_mul(1_000_000_000)
+                        .and_then(|nanos| env.block.time.nanos().checked_add(nanos))
+                        .ok_or(ContractError::InvalidIbcTransferTimeout)?,
+                    memo: memo_with_callback(memo, env.contract.address.as_str())?,
+                };
+
+                SubMsg::reply_on_success(transfer, IBC_TRANSFER_REPLY_ID)
+            }
+            Err(_) => SubMsg::new(WasmMsg::Execute {
+                contract_addr: recipient_address.into(),
+                msg: Binary::from(data),
+                funds: info.funds,
+            }),
+        },
+        None => SubMsg::new(BankMsg::Send {
             to_address: recipient_address.into(),
             amount: info.funds,
-        }
-        .into(),
+        }),
     };
 
     state::order_fills().create_order_fill(deps.storage, order_id, filler, order.source_domain)?;
 
-    Ok(Response::new().add_message(msg))
+    Ok(Response::new().add_submessage(msg))
+}
+
+/// Only called for transfers that were sent, a failing transfer fails the
+/// whole message that sent it, and with it the fill or refund.
+pub fn ibc_transfer_reply(deps: DepsMut, reply: Reply) -> ContractResponse {
+    let fallback = PENDING_IBC_TRANSFERS
+        .pop_front(deps.storage)?
+        .ok_or_else(|| StdError::not_found("pending IBC transfer"))?;
+
+    let data = reply
+        .result
+        .into_result()
+        .map_err(StdError::generic_err)?
+        .data
+        .unwrap_or_default();
+    let sequence = decode_msg_transfer_response(&data)?;
+
+    IBC_TRANSFERS.save(
+        deps.storage,
+        (fallback.channel_id.clone(), sequence),
+        &fallback,
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "ibc_transfer")
+        .add_attribute("order_id", fallback.order_id.to_string())
+        .add_attribute("channel_id", fallback.channel_id)
+        .add_attribute("sequence", sequence.to_string()))
+}
+
+pub fn ibc_lifecycle_complete(deps: DepsMut, msg: IbcLifecycleComplete) -> ContractResponse {
+    let (channel, sequence, success) = match msg {
+        IbcLifecycleComplete::IbcAck {
+            channel,
+            sequence,
+            success,
+            ..
+        } => (channel, sequence, success),
+        IbcLifecycleComplete::IbcTimeout { channel, sequence } => (channel, sequence, false),
+    };
+
+    let Some(fallback) = IBC_TRANSFERS.may_load(deps.storage, (channel.clone(), sequence))? else {
+        return Ok(Response::default());
+    };
+    IBC_TRANSFERS.remove(deps.storage, (channel, sequence));
+
+    if success {
+        return Ok(Response::new()
+            .add_attribute("action", "ibc_transfer_completed")
+            .add_attribute("order_id", fallback.order_id.to_string()));
+    }
+
+    if fallback.claimable {
+        CLAIMABLE_REFUNDS.update(
+            deps.storage,
+            (&fallback.fallback_address, &fallback.amount.denom),
+            |claimable| -> StdResult<_> {
+                Ok(claimable.unwrap_or_default() + fallback.amount.amount)
+            },
+        )?;
+
+        return Ok(Response::new()
+            .add_attribute("action", "ibc_refund_claimable")
+            .add_attribute("order_id", fallback.order_id.to_string())
+            .add_attribute("address", fallback.fallback_address));
+    }
+
+    Ok(Response::new()
+        .add_message(BankMsg::Send {
+            to_address: fallback.fallback_address.into(),
+            amount: vec![fallback.amount],
+        })
+        .add_attribute("action", "ibc_transfer_fallback")
+        .add_attribute("order_id", fallback.order_id.to_string()))
 }
 
 pub fn initiate_settlement(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     order_ids: Vec<HexBinary>,
     repayment_address: HexBinary,
 ) -> ContractResponse {
-    let config = CONFIG.load(deps.storage)?;
-
     if repayment_address.len() != 32 {
         return Err(ContractError::InvalidRepaymentAddress);
     }
@@ -108,6 +742,14 @@ pub fn initiate_settlement(
             return Err(ContractError::DuplicateOrder);
         }
 
+        match order_fill.settlement_status {
+            FillSettlementStatus::Unsettled => {}
+            FillSettlementStatus::Pending | FillSettlementStatus::Accepted => {
+                return Err(ContractError::FillSettlementPending)
+            }
+            FillSettlementStatus::Settled => return Err(ContractError::FillAlreadySettled),
+        }
+
         fills_to_settle.push(order_fill);
     }
 
@@ -126,24 +768,39 @@ pub fn initiate_settlement(
 
     let remote_contract_address = remote_contract_address.unwrap();
 
+    // Only IBC acknowledges settlements, so fills are only tracked as pending
+    // when settled over it. The ack or timeout of the packet moves them on.
+    let mut transports = vec![state::remote_domain_transport(deps.storage, source_domain)?];
+    if let Some(attestation) = REMOTE_DOMAIN_ATTESTATIONS.may_load(deps.storage, source_domain)? {
+        transports.push(attestation.transport);
+    }
+
+    if transports
+        .iter()
+        .any(|transport| matches!(transport, Transport::Ibc { .. }))
+    {
+        for order_id in &order_ids {
+            state::order_fills().set_settlement_status(
+                deps.storage,
+                order_id,
+                FillSettlementStatus::Pending,
+            )?;
+        }
+    }
+
     let settle_orders_message = SettleOrdersMessage {
         repayment_address,
         order_ids,
     };
 
-    let msg = WasmMsg::Execute {
-        contract_addr: config.mailbox_addr.clone(),
-        msg: to_json_binary(&MailboxExecuteMsg::Dispatch(DispatchMsg {
-            dest_domain: source_domain,
-            recipient_addr: remote_contract_address.clone(),
-            msg_body: settle_orders_message.encode(),
-            hook: Some(config.hook_addr.clone()),
-            metadata: None,
-        }))?,
-        funds: info.funds,
-    };
-
-    Ok(Response::new().add_message(msg))
+    dispatch_attested_command(
+        deps.as_ref(),
+        &env,
+        source_domain,
+        remote_contract_address,
+        settle_orders_message.encode(),
+        info.funds,
+    )
 }
 
 pub fn initiate_timeout(
@@ -152,8 +809,6 @@ pub fn initiate_timeout(
     info: MessageInfo,
     orders: Vec<FastTransferOrder>,
 ) -> ContractResponse {
-    let config = CONFIG.load(deps.storage)?;
-
     for order in &orders {
         assert_order_is_expired(&env, order)?;
         assert_order_not_filled(deps.as_ref(), order.id())?;
@@ -182,19 +837,14 @@ pub fn initiate_timeout(
 
     let timeout_orders_message = TimeoutOrdersMessage { order_ids };
 
-    let msg = WasmMsg::Execute {
-        contract_addr: config.mailbox_addr.clone(),
-        msg: to_json_binary(&MailboxExecuteMsg::Dispatch(DispatchMsg {
-            dest_domain: source_domain,
-            recipient_addr: remote_contract_address.clone(),
-            msg_body: timeout_orders_message.encode(),
-            hook: Some(config.hook_addr.clone()),
-            metadata: None,
-        }))?,
-        funds: info.funds,
-    };
-
-    Ok(Response::new().add_message(msg))
+    dispatch_attested_command(
+        deps.as_ref(),
+        &env,
+        source_domain,
+        remote_contract_address,
+        timeout_orders_message.encode(),
+        info.funds,
+    )
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -208,9 +858,18 @@ pub fn submit_order(
     destination_domain: u32,
     timeout_timestamp: u64,
     data: Option<HexBinary>,
+    refund_address: Option<String>,
+    ibc_origin: Option<IbcOrigin>,
 ) -> ContractResponse {
+    assert_not_paused(deps.as_ref())?;
+
     let config = CONFIG.load(deps.storage)?;
 
+    let refund_address = refund_address
+        .map(|addr| deps.api.addr_validate(&addr))
+        .transpose()?;
+
+    assert_order_sender(deps.as_ref(), &info, &sender)?;
     assert_correct_funds(&info, config.token_denom.as_str(), amount_in)?;
     assert_remote_domain(deps.as_ref(), destination_domain)?;
 
@@ -237,6 +896,8 @@ pub fn submit_order(
             nonce,
             destination_domain,
             amount: amount_in,
+            ibc_origin,
+            refund_address,
         },
     )?;
 
@@ -245,8 +906,52 @@ pub fn submit_order(
         .add_attributes(order.attributes()))
 }
 
+#[allow(clippy::too_many_arguments)]
+pub fn ibc_hook_submit_order(
+    deps: DepsMut,
+    info: MessageInfo,
+    ibc_origin: IbcOrigin,
+    recipient: HexBinary,
+    amount_in: Uint128,
+    amount_out: Uint128,
+    destination_domain: u32,
+    timeout_timestamp: u64,
+    data: Option<HexBinary>,
+) -> ContractResponse {
+    let config = CONFIG.load(deps.storage)?;
+
+    let intermediate_sender = derive_intermediate_sender(
+        &ibc_origin.channel_id,
+        &ibc_origin.sender,
+        &config.address_prefix,
+    )?;
+    if info.sender != intermediate_sender {
+        return Err(ContractError::Unauthorized);
+    }
+
+    let sender = HexBinary::from(left_pad_bytes(
+        bech32_decode(intermediate_sender.as_str())?,
+        32,
+    ));
+
+    submit_order(
+        deps,
+        info,
+        sender,
+        recipient,
+        amount_in,
+        amount_out,
+        destination_domain,
+        timeout_timestamp,
+        data,
+        None,
+        Some(ibc_origin),
+    )
+}
+
 pub fn handle(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     msg: hyperlane::message_recipient::HandleMsg,
 ) -> ContractResponse {
@@ -256,24 +961,153 @@ pub fn handle(
         return Err(ContractError::Unauthorized);
     }
 
-    let remote_contract = REMOTE_DOMAINS.load(deps.storage, msg.origin)?;
-    if msg.sender != remote_contract {
-        return Err(ContractError::Unauthorized);
+    let (response, _) = receive_command(
+        deps,
+        env,
+        Transport::Hyperlane,
+        msg.origin,
+        &msg.sender,
+        msg.body,
+    )?;
+
+    Ok(response)
+}
+
+/// Entry point for commands delivered by the relayer of a domain using the
+/// mock transport.
+pub fn receive_mock_command(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    origin: u32,
+    sender: HexBinary,
+    body: HexBinary,
+) -> ContractResponse {
+    let transport = Transport::Mock {
+        relayer: info.sender.into_string(),
+    };
+
+    let (response, _) = receive_command(deps, env, transport, origin, &sender, body)?;
+
+    Ok(response)
+}
+
+/// Pays out a settlement received from `msg_origin_domain`, or queues it when
+/// it takes the amount paid out to the domain within its settlement delay
+/// over the threshold. Counting paid amounts over a window keeps a large
+/// settlement from skipping the queue by being split into smaller ones.
+pub fn settle_orders(
+    deps: DepsMut,
+    env: &Env,
+    msg_origin_domain: u32,
+    msg: SettleOrdersMessage,
+) -> ContractResult<(Response, CommandStatus)> {
+    if let Some(settlement_delay) = SETTLEMENT_DELAYS.may_load(deps.storage, msg_origin_domain)? {
+        let amount = settlement_amount(deps.storage, msg_origin_domain, &msg.order_ids)?;
+
+        let window = SETTLEMENT_WINDOWS
+            .may_load(deps.storage, msg_origin_domain)?
+            .filter(|window| {
+                env.block.time
+                    < window
+                        .started_at
+                        .plus_seconds(settlement_delay.delay_seconds)
+            })
+            .unwrap_or(SettlementWindow {
+                started_at: env.block.time,
+                amount: Uint128::zero(),
+            });
+
+        if window.amount + amount > settlement_delay.threshold {
+            let response = queue_settlement(
+                deps,
+                env,
+                msg_origin_domain,
+                msg,
+                amount,
+                settlement_delay.delay_seconds,
+            )?;
+
+            return Ok((response, CommandStatus::Pending));
+        }
+
+        SETTLEMENT_WINDOWS.save(
+            deps.storage,
+            msg_origin_domain,
+            &SettlementWindow {
+                amount: window.amount + amount,
+                ..window
+            },
+        )?;
     }
 
-    let command: Command = msg.body.try_into().unwrap();
+    let response = pay_settlement(deps, msg_origin_domain, msg)?;
+
+    Ok((response, CommandStatus::Executed))
+}
+
+/// Total locked for the orders of a settlement that are still unfilled.
+fn settlement_amount(
+    storage: &dyn Storage,
+    msg_origin_domain: u32,
+    order_ids: &[HexBinary],
+) -> ContractResult<Uint128> {
+    let mut amount = Uint128::zero();
+
+    for order_id in order_ids {
+        let status = ORDER_STATUSES
+            .may_load(storage, order_id.to_vec())?
+            .unwrap_or_default();
 
-    match command {
-        Command::SettleOrders(settle_orders_message) => {
-            settle_orders(deps, msg.origin, settle_orders_message)
+        if status != OrderStatus::Unfilled {
+            continue;
         }
-        Command::TimeoutOrders(timeout_orders_message) => {
-            refund_orders(deps, msg.origin, timeout_orders_message)
+
+        let order_settlement_details = get_order_settlement_details(storage, order_id)?;
+        if order_settlement_details.destination_domain != msg_origin_domain {
+            return Err(ContractError::IncorrectDomainForSettlement);
         }
+
+        amount += order_settlement_details.amount;
     }
+
+    Ok(amount)
 }
 
-pub fn settle_orders(
+/// Order statuses are left untouched while a settlement is queued, they are
+/// checked again when it is executed.
+fn queue_settlement(
+    deps: DepsMut,
+    env: &Env,
+    msg_origin_domain: u32,
+    msg: SettleOrdersMessage,
+    amount: Uint128,
+    delay_seconds: u64,
+) -> ContractResponse {
+    let queued_settlement = QueuedSettlement {
+        id: state::next_queued_settlement_id(deps.storage)?,
+        domain: msg_origin_domain,
+        order_ids: msg.order_ids,
+        repayment_address: msg.repayment_address,
+        amount,
+        executable_at: env.block.time.plus_seconds(delay_seconds),
+    };
+
+    QUEUED_SETTLEMENTS.save(deps.storage, queued_settlement.id, &queued_settlement)?;
+
+    Ok(Response::new().add_event(
+        Event::new("settlement_queued")
+            .add_attribute("id", queued_settlement.id.to_string())
+            .add_attribute("domain", msg_origin_domain.to_string())
+            .add_attribute("amount", amount)
+            .add_attribute(
+                "executable_at",
+                queued_settlement.executable_at.seconds().to_string(),
+            ),
+    ))
+}
+
+fn pay_settlement(
     deps: DepsMut,
     msg_origin_domain: u32,
     msg: SettleOrdersMessage,
@@ -316,8 +1150,52 @@ pub fn settle_orders(
     Ok(Response::new().add_message(msg).add_attributes(attrs))
 }
 
+pub fn execute_queued_settlement(deps: DepsMut, env: Env, id: u64) -> ContractResponse {
+    let queued_settlement = QUEUED_SETTLEMENTS
+        .may_load(deps.storage, id)?
+        .ok_or(ContractError::QueuedSettlementNotFound(id))?;
+
+    if env.block.time < queued_settlement.executable_at {
+        return Err(ContractError::QueuedSettlementNotExecutable(
+            queued_settlement.executable_at.seconds(),
+        ));
+    }
+
+    QUEUED_SETTLEMENTS.remove(deps.storage, id);
+
+    let response = pay_settlement(
+        deps,
+        queued_settlement.domain,
+        SettleOrdersMessage {
+            order_ids: queued_settlement.order_ids,
+            repayment_address: queued_settlement.repayment_address,
+        },
+    )?;
+
+    Ok(response
+        .add_attribute("action", "execute_queued_settlement")
+        .add_attribute("id", id.to_string()))
+}
+
+/// Drops a queued settlement without paying it out. The orders it settled
+/// stay unfilled, so they can still be refunded or settled again.
+pub fn veto_queued_settlement(deps: DepsMut, info: MessageInfo, id: u64) -> ContractResponse {
+    assert_role(deps.as_ref(), &info.sender, Role::Guardian)?;
+
+    if !QUEUED_SETTLEMENTS.has(deps.storage, id) {
+        return Err(ContractError::QueuedSettlementNotFound(id));
+    }
+
+    QUEUED_SETTLEMENTS.remove(deps.storage, id);
+
+    Ok(Response::new()
+        .add_attribute("action", "veto_queued_settlement")
+        .add_attribute("id", id.to_string()))
+}
+
 pub fn refund_orders(
     deps: DepsMut,
+    env: Env,
     msg_origin_domain: u32,
     msg: TimeoutOrdersMessage,
 ) -> ContractResponse {
@@ -340,21 +1218,105 @@ pub fn refund_orders(
             return Err(ContractError::IncorrectDomainForSettlement);
         }
 
-        let sender = bech32_encode(&config.address_prefix, &order_settlement_details.sender)?;
-
-        let msg = BankMsg::Send {
-            to_address: sender.into(),
-            amount: vec![coin(
-                order_settlement_details.amount.u128(),
-                config.token_denom.clone(),
-            )],
-        };
-
-        msgs.push(msg);
+        msgs.push(refund_order_msg(
+            deps.storage,
+            &env,
+            &config,
+            &order_id,
+            order_settlement_details,
+        )?);
         attrs.push(("action".to_string(), "order_refunded".to_string()));
         attrs.push(("order_id".to_string(), order_id.to_string()));
         ORDER_STATUSES.save(deps.storage, order_id.to_vec(), &OrderStatus::Refunded)?;
     }
 
-    Ok(Response::new().add_messages(msgs).add_attributes(attrs))
+    Ok(Response::new().add_submessages(msgs).add_attributes(attrs))
+}
+
+/// Returns the funds locked for an order to its refund address, the IBC
+/// origin it was submitted from, or its sender, in that order of preference.
+/// Refunds over IBC are tracked like IBC transfer fills, and credited to the
+/// order's ibc-hooks sender to claim if they fail.
+fn refund_order_msg(
+    storage: &mut dyn Storage,
+    env: &Env,
+    config: &Config,
+    order_id: &HexBinary,
+    order_settlement_details: SettlementDetails,
+) -> ContractResult<SubMsg> {
+    let amount = coin(
+        order_settlement_details.amount.u128(),
+        config.token_denom.clone(),
+    );
+    let sender = bech32_encode(&config.address_prefix, &order_settlement_details.sender)?;
+
+    let msg = match (
+        order_settlement_details.refund_address,
+        order_settlement_details.ibc_origin,
+    ) {
+        (Some(refund_address), _) => SubMsg::new(BankMsg::Send {
+            to_address: refund_address.into(),
+            amount: vec![amount],
+        }),
+        (None, Some(ibc_origin)) => {
+            PENDING_IBC_TRANSFERS.push_back(
+                storage,
+                &IbcTransferFallback {
+                    order_id: order_id.clone(),
+                    channel_id: ibc_origin.channel_id.clone(),
+                    fallback_address: sender,
+                    amount: amount.clone(),
+                    claimable: true,
+                },
+            )?;
+
+            let transfer = MsgTransfer {
+                source_port: "transfer".to_string(),
+                source_channel: ibc_origin.channel_id,
+                token: amount,
+                sender: env.contract.address.to_string(),
+                receiver: ibc_origin.sender,
+                timeout_timestamp: env
+                    .block
+                    .time
+                    .plus_seconds(IBC_REFUND_TIMEOUT_SECONDS)
+                    .nanos(),
+                memo: memo_with_callback(None, env.contract.address.as_str())?,
+            };
+
+            SubMsg::reply_on_success(transfer, IBC_TRANSFER_REPLY_ID)
+        }
+        (None, None) => SubMsg::new(BankMsg::Send {
+            to_address: sender.into(),
+            amount: vec![amount],
+        }),
+    };
+
+    Ok(msg)
+}
+
+pub fn claim_refunds(deps: DepsMut, info: MessageInfo, recipient: String) -> ContractResponse {
+    let recipient = deps.api.addr_validate(&recipient)?;
+
+    let refunds = CLAIMABLE_REFUNDS
+        .prefix(&info.sender)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|entry| entry.map(|(denom, amount)| coin(amount.u128(), denom)))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    if refunds.is_empty() {
+        return Err(ContractError::NoClaimableRefunds);
+    }
+
+    for refund in &refunds {
+        CLAIMABLE_REFUNDS.remove(deps.storage, (&info.sender, &refund.denom));
+    }
+
+    Ok(Response::new()
+        .add_message(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: refunds,
+        })
+        .add_attribute("action", "claim_refunds")
+        .add_attribute("recipient", recipient))
 }
@@ -1,6 +1,6 @@
 use cosmwasm_std::{Addr, Deps, HexBinary, Order as ListOrder, StdResult, Storage};
 use cw_storage_plus::{Bound, Index, IndexList, IndexedMap, MultiIndex};
-use go_fast::gateway::OrderFill;
+use go_fast::gateway::{FillSettlementStatus, OrderFill};
 
 pub struct Fills<'a> {
     fills: IndexedMap<'a, Vec<u8>, OrderFill, FillIndexes<'a>>,
@@ -46,6 +46,7 @@ impl<'a> Fills<'a> {
             order_id: order_id.clone(),
             filler,
             source_domain,
+            settlement_status: FillSettlementStatus::Unsettled,
         };
 
         self.fills.save(storage, order_id.to_vec(), &fill)?;
@@ -53,6 +54,27 @@ impl<'a> Fills<'a> {
         Ok(fill)
     }
 
+    /// Updates the settlement status of the fill for `order_id`, if this
+    /// gateway filled it. A settled fill stays settled, the ack or timeout of
+    /// a packet sent over another transport can arrive after it.
+    pub fn set_settlement_status(
+        &self,
+        storage: &mut dyn Storage,
+        order_id: &HexBinary,
+        settlement_status: FillSettlementStatus,
+    ) -> StdResult<()> {
+        let Some(mut fill) = self.fills.may_load(storage, order_id.to_vec())? else {
+            return Ok(());
+        };
+
+        if fill.settlement_status == FillSettlementStatus::Settled {
+            return Ok(());
+        }
+
+        fill.settlement_status = settlement_status;
+        self.fills.save(storage, order_id.to_vec(), &fill)
+    }
+
     pub fn by_order_id(&self, deps: Deps, order_id: HexBinary) -> StdResult<OrderFill> {
         self.fills.load(deps.storage, order_id.to_vec())
     }
@@ -1,14 +1,24 @@
 use crate::{
     error::{ContractError, ContractResult},
     msg::SettlementDetails,
-    state::{self, LOCAL_DOMAIN, REMOTE_DOMAINS, SETTLEMENT_DETAILS},
+    state::{
+        self, ALLOWED_SUBMITTERS, DISABLED_REMOTE_DOMAINS, DOMAIN_SUNSETS, LOCAL_DOMAIN, PAUSED,
+        REMOTE_DOMAINS, REMOTE_DOMAIN_ATTESTATIONS, REMOTE_DOMAIN_ROTATIONS, ROLES,
+        SETTLEMENT_DETAILS,
+    },
 };
 use bech32::{Bech32, Hrp};
 use cosmwasm_std::{
     coins, Addr, Deps, Env, HexBinary, MessageInfo, StdError, StdResult, Storage, Timestamp,
     Uint128,
 };
-use go_fast::FastTransferOrder;
+use go_fast::{
+    gateway::{Config, ConfigUpdate, Role, TimelockedAction, Transport},
+    FastTransferOrder,
+};
+use hyperlane::mailbox::{get_default_hook, get_required_hook};
+
+const IBC_HOOKS_SENDER_PREFIX: &str = "ibc-wasm-hook-intermediary";
 
 /// Asserts that exactly `amount` of `denom` is sent to the contract, with no
 /// extra funds.
@@ -27,6 +37,157 @@ pub fn assert_correct_funds(
     Ok(())
 }
 
+/// Passes if `sender` holds `role`, or is the owner, who holds every role.
+pub fn assert_role(deps: Deps, sender: &Addr, role: Role) -> ContractResult<()> {
+    if ROLES.has(deps.storage, (role.as_str(), sender))
+        || cw_ownable::is_owner(deps.storage, sender)?
+    {
+        return Ok(());
+    }
+
+    Err(ContractError::MissingRole(role.as_str()))
+}
+
+/// Remote domain changes can be proposed by the domain manager, settlement
+/// delays by the rate limit manager and hook changes by the fee manager.
+/// Everything else, including ISM overrides, only by the owner.
+pub fn assert_can_propose(
+    deps: Deps,
+    sender: &Addr,
+    action: &TimelockedAction,
+) -> ContractResult<()> {
+    match action {
+        TimelockedAction::SetRemoteDomain { .. }
+        | TimelockedAction::RemoveRemoteDomain { .. }
+        | TimelockedAction::SetRemoteDomainTransport { .. }
+        | TimelockedAction::SetRemoteDomainAttestation { .. } => {
+            assert_role(deps, sender, Role::DomainManager)
+        }
+        TimelockedAction::SetSettlementDelay { .. } => {
+            assert_role(deps, sender, Role::RateLimitManager)
+        }
+        TimelockedAction::UpdateConfig(ConfigUpdate {
+            token_denom: None,
+            address_prefix: None,
+            mailbox_addr: None,
+            hook_addr: Some(_),
+        }) => assert_role(deps, sender, Role::FeeManager),
+        TimelockedAction::UpdateConfig { .. }
+        | TimelockedAction::UpdateTimelockDelay { .. }
+        | TimelockedAction::UpdateRotationGracePeriod { .. }
+        | TimelockedAction::SetInterchainSecurityModule { .. }
+        | TimelockedAction::SetRemoteDomainIsm { .. } => {
+            cw_ownable::assert_owner(deps.storage, sender)?;
+            Ok(())
+        }
+    }
+}
+
+/// Asserts that a domain's attestation transport stays independent from its
+/// primary transport once `action` is applied.
+pub fn assert_independent_transports(
+    storage: &dyn Storage,
+    action: &TimelockedAction,
+) -> ContractResult<()> {
+    let (transport, attestation) = match action {
+        TimelockedAction::SetRemoteDomainTransport { domain, transport } => (
+            transport.clone(),
+            REMOTE_DOMAIN_ATTESTATIONS.may_load(storage, *domain)?,
+        ),
+        TimelockedAction::SetRemoteDomainAttestation {
+            domain,
+            attestation,
+        } => (
+            state::remote_domain_transport(storage, *domain)?,
+            attestation.clone(),
+        ),
+        _ => return Ok(()),
+    };
+
+    if attestation.is_some_and(|attestation| attestation.transport == transport) {
+        return Err(ContractError::AttestationTransportNotIndependent);
+    }
+
+    Ok(())
+}
+
+/// Asserts that the IBC channel `action` binds a domain to is not already
+/// carrying the commands of another domain.
+pub fn assert_ibc_channel_available(
+    storage: &dyn Storage,
+    action: &TimelockedAction,
+) -> ContractResult<()> {
+    let (domain, transport) = match action {
+        TimelockedAction::SetRemoteDomainTransport { domain, transport } => (domain, transport),
+        TimelockedAction::SetRemoteDomainAttestation {
+            domain,
+            attestation: Some(attestation),
+        } => (domain, &attestation.transport),
+        _ => return Ok(()),
+    };
+
+    if let Transport::Ibc { channel_id } = transport {
+        match state::ibc_channel_domain(storage, channel_id)? {
+            Some(bound) if bound != *domain => {
+                return Err(ContractError::IbcChannelInUse(channel_id.clone(), bound))
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks a config before it is stored: the denom and bech32 prefix must be
+/// well formed, the addresses valid and the mailbox must answer the hook
+/// queries the gateway relies on for dispatching.
+pub fn validate_config(deps: Deps, config: &Config) -> ContractResult<()> {
+    if !is_valid_denom(&config.token_denom) {
+        return Err(ContractError::InvalidConfig(
+            "token_denom is not a valid denom".into(),
+        ));
+    }
+
+    if Hrp::parse(&config.address_prefix).is_err()
+        || config.address_prefix != config.address_prefix.to_lowercase()
+    {
+        return Err(ContractError::InvalidConfig(
+            "address_prefix is not a valid bech32 prefix".into(),
+        ));
+    }
+
+    deps.api.addr_validate(&config.mailbox_addr)?;
+    deps.api.addr_validate(&config.hook_addr)?;
+
+    if get_default_hook(deps, &config.mailbox_addr).is_err()
+        || get_required_hook(deps, &config.mailbox_addr).is_err()
+    {
+        return Err(ContractError::InvalidConfig(
+            "mailbox_addr does not respond to mailbox queries".into(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Mirrors the Cosmos SDK denom format: a letter followed by 2 to 127 letters,
+/// digits or one of `/:._-`.
+fn is_valid_denom(denom: &str) -> bool {
+    let mut chars = denom.chars();
+
+    (3..=128).contains(&denom.len())
+        && chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || "/:._-".contains(c))
+}
+
+pub fn assert_not_paused(deps: Deps) -> ContractResult<()> {
+    if PAUSED.may_load(deps.storage)?.unwrap_or_default() {
+        return Err(ContractError::Paused);
+    }
+
+    Ok(())
+}
+
 pub fn assert_order_not_filled(deps: Deps, order_id: HexBinary) -> ContractResult<()> {
     if state::order_fills()
         .by_order_id(deps, order_id.clone())
@@ -64,11 +225,67 @@ pub fn assert_local_domain(deps: Deps, domain: u32) -> ContractResult<()> {
     Ok(())
 }
 
+/// Asserts that `domain` is known and enabled. Only new orders and fills go
+/// through this check, settlements and refunds for a disabled domain still
+/// go through.
 pub fn assert_remote_domain(deps: Deps, domain: u32) -> ContractResult<()> {
     if !REMOTE_DOMAINS.has(deps.storage, domain) {
         return Err(ContractError::UnknownRemoteDomain);
     }
 
+    if DISABLED_REMOTE_DOMAINS.has(deps.storage, domain) {
+        return Err(ContractError::RemoteDomainDisabled);
+    }
+
+    if DOMAIN_SUNSETS.has(deps.storage, domain) {
+        return Err(ContractError::RemoteDomainSunset);
+    }
+
+    Ok(())
+}
+
+/// Asserts that `sender` is the router for `domain`, or the router it was
+/// rotated away from while that rotation's grace period lasts.
+pub fn assert_remote_sender(
+    deps: Deps,
+    env: &Env,
+    domain: u32,
+    sender: &HexBinary,
+) -> ContractResult<()> {
+    let remote_contract = REMOTE_DOMAINS
+        .may_load(deps.storage, domain)?
+        .ok_or(ContractError::UnknownRemoteDomain)?;
+    if *sender == remote_contract {
+        return Ok(());
+    }
+
+    match REMOTE_DOMAIN_ROTATIONS.may_load(deps.storage, domain)? {
+        Some(rotation)
+            if rotation.previous_address == *sender && env.block.time < rotation.expires_at =>
+        {
+            Ok(())
+        }
+        _ => Err(ContractError::Unauthorized),
+    }
+}
+
+/// Asserts that `sender` is the left padded caller address, unless the caller
+/// is an allowed submitter that may submit orders on behalf of other senders.
+pub fn assert_order_sender(
+    deps: Deps,
+    info: &MessageInfo,
+    sender: &HexBinary,
+) -> ContractResult<()> {
+    if ALLOWED_SUBMITTERS.has(deps.storage, info.sender.clone()) {
+        return Ok(());
+    }
+
+    let caller =
+        bech32_decode(info.sender.as_str()).map_err(|_| ContractError::InvalidOrderSender)?;
+    if caller.len() > 32 || left_pad_bytes(caller, 32) != sender.to_vec() {
+        return Err(ContractError::InvalidOrderSender);
+    }
+
     Ok(())
 }
 
@@ -103,6 +320,27 @@ pub fn bech32_encode(hrp: &str, raw_addr: &[u8]) -> StdResult<Addr> {
     Ok(Addr::unchecked(enc_addr))
 }
 
+/// Derives the address ibc-hooks executes wasm memos from for packets received
+/// on `channel_id` from `original_sender`.
+pub fn derive_intermediate_sender(
+    channel_id: &str,
+    original_sender: &str,
+    hrp: &str,
+) -> StdResult<Addr> {
+    use sha2::{Digest, Sha256};
+
+    let type_hash = Sha256::digest(IBC_HOOKS_SENDER_PREFIX.as_bytes());
+    let mut hasher = Sha256::new();
+    hasher.update(type_hash);
+    hasher.update(format!("{channel_id}/{original_sender}").as_bytes());
+    let hash = hasher.finalize();
+
+    let enc_addr = bech32::encode::<Bech32>(Hrp::parse_unchecked(hrp), &hash)
+        .map_err(|e| StdError::generic_err(format!("invalid bech32 address. err: {e}")))?;
+
+    Ok(Addr::unchecked(enc_addr))
+}
+
 pub fn left_pad_bytes(bytes: Vec<u8>, length: usize) -> Vec<u8> {
     let mut padded = vec![0u8; length];
     let start = length - bytes.len();
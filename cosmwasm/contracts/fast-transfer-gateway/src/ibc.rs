@@ -0,0 +1,188 @@
+use cosmwasm_std::{
+    entry_point, from_json, to_json_binary, Binary, DepsMut, Env, HexBinary, IbcBasicResponse,
+    IbcChannel, IbcChannelCloseMsg, IbcChannelConnectMsg, IbcChannelOpenMsg,
+    IbcChannelOpenResponse, IbcOrder, IbcPacket, IbcPacketAckMsg, IbcPacketReceiveMsg,
+    IbcPacketTimeoutMsg, IbcReceiveResponse, StdError, Storage,
+};
+use go_fast::gateway::{FillSettlementStatus, Transport};
+
+use crate::{
+    contract::IBC_APP_VERSION,
+    error::{ContractError, ContractResult},
+    helpers::{bech32_decode, left_pad_bytes},
+    msg::{Command, CommandAck},
+    state,
+    transport::{receive_command, CommandStatus},
+};
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_channel_open(
+    _deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelOpenMsg,
+) -> ContractResult<IbcChannelOpenResponse> {
+    validate_channel(msg.channel(), msg.counterparty_version())
+}
+
+/// Any gateway can connect a channel, commands received over it are only
+/// accepted once a remote domain is bound to it through its transport.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_channel_connect(
+    _deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelConnectMsg,
+) -> ContractResult<IbcBasicResponse> {
+    let channel = msg.channel();
+
+    validate_channel(channel, msg.counterparty_version())?;
+
+    Ok(IbcBasicResponse::new()
+        .add_attribute("action", "ibc_channel_connect")
+        .add_attribute("channel_id", &channel.endpoint.channel_id)
+        .add_attribute(
+            "counterparty_port_id",
+            &channel.counterparty_endpoint.port_id,
+        ))
+}
+
+/// Packets still in flight on a closed channel time out, which leaves the
+/// fills they were settling retryable over a new channel.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_channel_close(
+    _deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelCloseMsg,
+) -> ContractResult<IbcBasicResponse> {
+    Ok(IbcBasicResponse::new()
+        .add_attribute("action", "ibc_channel_close")
+        .add_attribute("channel_id", &msg.channel().endpoint.channel_id))
+}
+
+/// Executes a command sent by the gateway of the domain bound to the channel.
+/// Errors are turned into error acks by the chain, reverting any state change.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_packet_receive(
+    deps: DepsMut,
+    env: Env,
+    msg: IbcPacketReceiveMsg,
+) -> ContractResult<IbcReceiveResponse> {
+    let packet = msg.packet;
+    let channel_id = packet.dest.channel_id;
+
+    let origin = state::ibc_channel_domain(deps.storage, &channel_id)?
+        .ok_or_else(|| ContractError::UnknownIbcChannel(channel_id.clone()))?;
+    let sender = counterparty_gateway(&packet.src.port_id)?;
+
+    let (response, status) = receive_command(
+        deps,
+        env,
+        Transport::Ibc { channel_id },
+        origin,
+        &sender,
+        packet.data.into(),
+    )?;
+
+    // Settlements held back are not acked as results, the sending gateway
+    // would consider its fills settled.
+    let ack = match status {
+        CommandStatus::Executed => CommandAck::Result(Binary::default()),
+        CommandStatus::Pending => CommandAck::Pending {},
+    };
+
+    Ok(IbcReceiveResponse::new()
+        .set_ack(to_json_binary(&ack)?)
+        .add_submessages(response.messages)
+        .add_attributes(response.attributes)
+        .add_events(response.events))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_packet_ack(
+    deps: DepsMut,
+    _env: Env,
+    msg: IbcPacketAckMsg,
+) -> ContractResult<IbcBasicResponse> {
+    let ack = from_json(&msg.acknowledgement.data);
+    let success = matches!(ack, Ok(CommandAck::Result(_)));
+
+    // A rejected settlement leaves the fills retryable, same as a timeout. A
+    // pending one is still in flight on the remote gateway, it is paid out
+    // once attested over its other transport or once its queue delay passed.
+    let settlement_status = match ack {
+        Ok(CommandAck::Result(_)) => FillSettlementStatus::Settled,
+        Ok(CommandAck::Pending {}) => FillSettlementStatus::Accepted,
+        _ => FillSettlementStatus::Unsettled,
+    };
+
+    let order_ids = update_fills(deps.storage, &msg.original_packet, settlement_status)?;
+
+    Ok(IbcBasicResponse::new()
+        .add_attribute("action", "ibc_command_ack")
+        .add_attribute("success", success.to_string())
+        .add_attributes(order_ids.iter().map(|id| ("order_id", id.to_string()))))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn ibc_packet_timeout(
+    deps: DepsMut,
+    _env: Env,
+    msg: IbcPacketTimeoutMsg,
+) -> ContractResult<IbcBasicResponse> {
+    let order_ids = update_fills(deps.storage, &msg.packet, FillSettlementStatus::Unsettled)?;
+
+    Ok(IbcBasicResponse::new()
+        .add_attribute("action", "ibc_command_timeout")
+        .add_attributes(order_ids.iter().map(|id| ("order_id", id.to_string()))))
+}
+
+fn validate_channel(
+    channel: &IbcChannel,
+    counterparty_version: Option<&str>,
+) -> ContractResult<()> {
+    if channel.order != IbcOrder::Unordered {
+        return Err(ContractError::InvalidIbcChannelOrder);
+    }
+
+    for version in [Some(channel.version.as_str()), counterparty_version]
+        .into_iter()
+        .flatten()
+    {
+        if version != IBC_APP_VERSION {
+            return Err(ContractError::InvalidIbcVersion(version.to_string()));
+        }
+    }
+
+    Ok(())
+}
+
+/// The gateway bound to a counterparty wasm port, in the left padded form
+/// remote domain addresses are stored in.
+fn counterparty_gateway(port_id: &str) -> ContractResult<HexBinary> {
+    let address = port_id
+        .strip_prefix("wasm.")
+        .ok_or(ContractError::Unauthorized)?;
+
+    Ok(HexBinary::from(left_pad_bytes(bech32_decode(address)?, 32)))
+}
+
+/// Moves the fills settled by a packet this gateway sent to
+/// `settlement_status`, returning their order ids. Timeout commands carry no
+/// fills.
+fn update_fills(
+    storage: &mut dyn Storage,
+    packet: &IbcPacket,
+    settlement_status: FillSettlementStatus,
+) -> ContractResult<Vec<HexBinary>> {
+    let body = HexBinary::from(packet.data.clone());
+    let command: Command = body.try_into().map_err(StdError::generic_err)?;
+
+    let Command::SettleOrders(settle_orders_message) = command else {
+        return Ok(vec![]);
+    };
+
+    for order_id in &settle_orders_message.order_ids {
+        state::order_fills().set_settlement_status(storage, order_id, settlement_status.clone())?;
+    }
+
+    Ok(settle_orders_message.order_ids)
+}
@@ -3,6 +3,9 @@ pub mod error;
 pub mod execute;
 pub mod fills;
 pub mod helpers;
+pub mod ibc;
 pub mod msg;
 pub mod query;
 pub mod state;
+pub mod transfer;
+pub mod transport;
@@ -1,16 +1,9 @@
 use std::vec;
 
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{HexBinary, Uint128};
+use cosmwasm_std::{Addr, Binary, Coin, HexBinary, Timestamp, Uint128};
 
-#[cw_serde]
-#[derive(Default)]
-pub enum OrderStatus {
-    #[default]
-    Unfilled,
-    Filled,
-    Refunded,
-}
+pub use go_fast::gateway::{IbcOrigin, OrderStatus};
 
 #[cw_serde]
 pub enum Command {
@@ -63,6 +56,29 @@ pub struct SettlementDetails {
     pub nonce: u32,
     pub destination_domain: u32,
     pub amount: Uint128,
+    pub ibc_origin: Option<IbcOrigin>,
+    pub refund_address: Option<Addr>,
+}
+
+/// Amount paid out to the settlements of a domain since `started_at`, counted
+/// against its settlement delay threshold until `delay_seconds` have passed.
+#[cw_serde]
+pub struct SettlementWindow {
+    pub started_at: Timestamp,
+    pub amount: Uint128,
+}
+
+#[cw_serde]
+pub struct IbcTransferFallback {
+    pub order_id: HexBinary,
+    pub channel_id: String,
+    pub fallback_address: Addr,
+    pub amount: Coin,
+    /// Credits the amount of a failed transfer to `fallback_address` to be
+    /// claimed with `ClaimRefunds`, instead of sending it. Used for refunds
+    /// to ibc-hooks senders, which can only act through the gateway.
+    #[serde(default)]
+    pub claimable: bool,
 }
 
 #[cw_serde]
@@ -98,3 +114,15 @@ impl TimeoutOrdersMessage {
             .into()
     }
 }
+
+/// Acknowledgement written for commands received over IBC. It has the shape of
+/// the ICS-04 acknowledgement JSON, so the error acks the chain writes when a
+/// command fails decode as `Error`. `Pending` is written for settlements the
+/// gateway accepted but has not paid out yet, which may still expire or be
+/// vetoed.
+#[cw_serde]
+pub enum CommandAck {
+    Result(Binary),
+    Pending {},
+    Error(String),
+}
@@ -1,10 +1,28 @@
-use cosmwasm_std::{Addr, Coin, Deps, HexBinary, Order, StdError, StdResult};
-use go_fast::gateway::{Config, OrderFill, RemoteDomain};
-use hyperlane::mailbox::{quote_dispatch, DispatchMsg};
+use cosmwasm_std::{Addr, Coin, Deps, Env, HexBinary, Order, StdError, StdResult};
+use cw_storage_plus::Bound;
+use go_fast::gateway::{
+    Config, DomainSunset, OrderFill, OrderStatusResponse, PendingAttestation, Proposal,
+    QueuedSettlement, RemoteDomain, Role, RoleGrant,
+};
+use hyperlane::{
+    ism::{
+        verify, InterchainSecurityModuleResponse, IsmType, ModuleTypeResponse, RouteResponse,
+        VerifyResponse,
+    },
+    mailbox::get_default_ism,
+    message::origin_domain,
+};
 
 use crate::{
     helpers::encode_settle_order_data,
-    state::{self, CONFIG, LOCAL_DOMAIN, REMOTE_DOMAINS},
+    state::{
+        self, ALLOWED_SUBMITTERS, CLAIMABLE_REFUNDS, CONFIG, DISABLED_REMOTE_DOMAINS,
+        DOMAIN_SUNSETS, INTERCHAIN_SECURITY_MODULE, LOCAL_DOMAIN, NONCE, ORDER_STATUSES, PAUSED,
+        PENDING_ATTESTATIONS, PROPOSALS, QUEUED_SETTLEMENTS, REMOTE_DOMAINS,
+        REMOTE_DOMAIN_ATTESTATIONS, REMOTE_DOMAIN_ISMS, REMOTE_DOMAIN_ROTATIONS, ROLES,
+        SETTLEMENT_DELAYS, SETTLEMENT_DETAILS,
+    },
+    transport::quote_dispatch_attested_command,
 };
 
 pub fn get_config(deps: Deps) -> StdResult<Config> {
@@ -17,6 +35,11 @@ pub fn get_local_domain(deps: Deps) -> StdResult<u32> {
     Ok(local_domain)
 }
 
+pub fn get_next_nonce(deps: Deps) -> StdResult<u32> {
+    let nonce = NONCE.load(deps.storage)?;
+    Ok(nonce + 1)
+}
+
 pub fn get_remote_domain(deps: Deps, domain: u32) -> StdResult<HexBinary> {
     let remote_domain = REMOTE_DOMAINS.load(deps.storage, domain)?;
     Ok(remote_domain)
@@ -28,17 +51,153 @@ pub fn get_remote_domains(deps: Deps) -> StdResult<Vec<RemoteDomain>> {
         .map(|entry| {
             let (domain, address) = entry?;
 
-            Ok(RemoteDomain { domain, address })
+            Ok(RemoteDomain {
+                domain,
+                address,
+                enabled: !DISABLED_REMOTE_DOMAINS.has(deps.storage, domain),
+                rotation: REMOTE_DOMAIN_ROTATIONS.may_load(deps.storage, domain)?,
+                transport: state::remote_domain_transport(deps.storage, domain)?,
+                attestation: REMOTE_DOMAIN_ATTESTATIONS.may_load(deps.storage, domain)?,
+                settlement_delay: SETTLEMENT_DELAYS.may_load(deps.storage, domain)?,
+                ism: REMOTE_DOMAIN_ISMS.may_load(deps.storage, domain)?,
+            })
         })
         .collect::<StdResult<Vec<RemoteDomain>>>()?;
 
     Ok(remote_domains)
 }
 
+pub fn get_rotation_grace_period(deps: Deps) -> StdResult<u64> {
+    state::rotation_grace_period(deps.storage)
+}
+
+pub fn get_domain_sunsets(deps: Deps) -> StdResult<Vec<DomainSunset>> {
+    DOMAIN_SUNSETS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|entry| entry.map(|(_, sunset)| sunset))
+        .collect()
+}
+
+pub fn get_claimable_refunds(deps: Deps, address: String) -> StdResult<Vec<Coin>> {
+    let address = deps.api.addr_validate(&address)?;
+
+    CLAIMABLE_REFUNDS
+        .prefix(&address)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|entry| entry.map(|(denom, amount)| Coin { denom, amount }))
+        .collect()
+}
+
+pub fn get_pending_attestations(
+    deps: Deps,
+    domain: u32,
+    start_after: Option<HexBinary>,
+    limit: Option<u32>,
+) -> StdResult<Vec<PendingAttestation>> {
+    let limit = limit.unwrap_or(10) as usize;
+    let start = start_after.map(|payload_hash| Bound::exclusive(payload_hash.to_vec()));
+
+    PENDING_ATTESTATIONS
+        .prefix(domain)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|entry| entry.map(|(_, pending)| pending))
+        .collect()
+}
+
+pub fn get_allowed_submitters(deps: Deps) -> StdResult<Vec<Addr>> {
+    ALLOWED_SUBMITTERS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect()
+}
+
+pub fn get_roles(deps: Deps, role: Option<Role>) -> StdResult<Vec<RoleGrant>> {
+    match role {
+        Some(role) => ROLES
+            .prefix(role.as_str())
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|entry| {
+                let (address, role) = entry?;
+                Ok(RoleGrant { role, address })
+            })
+            .collect(),
+        None => ROLES
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|entry| {
+                let ((_, address), role) = entry?;
+                Ok(RoleGrant { role, address })
+            })
+            .collect(),
+    }
+}
+
+pub fn get_paused(deps: Deps) -> StdResult<bool> {
+    Ok(PAUSED.may_load(deps.storage)?.unwrap_or_default())
+}
+
+pub fn get_timelock_delay(deps: Deps) -> StdResult<u64> {
+    state::timelock_delay(deps.storage)
+}
+
+pub fn get_queued_settlement(deps: Deps, id: u64) -> StdResult<QueuedSettlement> {
+    QUEUED_SETTLEMENTS.load(deps.storage, id)
+}
+
+pub fn get_queued_settlements(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<QueuedSettlement>> {
+    let limit = limit.unwrap_or(10) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    QUEUED_SETTLEMENTS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|entry| entry.map(|(_, queued_settlement)| queued_settlement))
+        .collect()
+}
+
+pub fn get_proposal(deps: Deps, id: u64) -> StdResult<Proposal> {
+    PROPOSALS.load(deps.storage, id)
+}
+
+pub fn get_proposals(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<Proposal>> {
+    let limit = limit.unwrap_or(10) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    PROPOSALS
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|entry| entry.map(|(_, proposal)| proposal))
+        .collect()
+}
+
 pub fn get_order_fill(deps: Deps, order_id: HexBinary) -> StdResult<OrderFill> {
     state::order_fills().by_order_id(deps, order_id)
 }
 
+pub fn get_order_status(deps: Deps, order_id: HexBinary) -> StdResult<OrderStatusResponse> {
+    let settlement_details = SETTLEMENT_DETAILS.load(deps.storage, order_id.to_vec())?;
+    let status = ORDER_STATUSES
+        .may_load(deps.storage, order_id.to_vec())?
+        .unwrap_or_default();
+
+    Ok(OrderStatusResponse {
+        order_id,
+        status,
+        sender: settlement_details.sender,
+        destination_domain: settlement_details.destination_domain,
+        amount: settlement_details.amount,
+        refund_address: settlement_details.refund_address,
+        ibc_origin: settlement_details.ibc_origin,
+    })
+}
+
 pub fn order_fills_by_filler(
     deps: Deps,
     filler: Addr,
@@ -55,22 +214,69 @@ pub fn quote_initiate_settlement(
     repayment_address: HexBinary,
     source_domain: u32,
 ) -> StdResult<Vec<Coin>> {
-    let config = CONFIG.load(deps.storage)?;
+    let remote_contract_address = REMOTE_DOMAINS
+        .may_load(deps.storage, source_domain)?
+        .ok_or_else(|| StdError::generic_err("Unknown remote domain"))?;
+
+    quote_dispatch_attested_command(
+        deps,
+        source_domain,
+        remote_contract_address,
+        encode_settle_order_data(repayment_address, order_ids),
+    )
+}
 
-    let remote_contract_address = REMOTE_DOMAINS.may_load(deps.storage, source_domain)?;
-    if remote_contract_address.is_none() {
-        return Err(StdError::generic_err("Unknown remote domain"));
+pub fn get_interchain_security_module(
+    deps: Deps,
+    env: Env,
+) -> StdResult<InterchainSecurityModuleResponse> {
+    if REMOTE_DOMAIN_ISMS.is_empty(deps.storage) {
+        return Ok(InterchainSecurityModuleResponse {
+            ism: INTERCHAIN_SECURITY_MODULE.may_load(deps.storage)?,
+        });
+    }
+
+    Ok(InterchainSecurityModuleResponse {
+        ism: Some(env.contract.address),
+    })
+}
+
+pub fn get_ism_module_type() -> ModuleTypeResponse {
+    ModuleTypeResponse {
+        typ: IsmType::Routing,
     }
+}
 
-    let remote_contract_address = remote_contract_address.unwrap();
+pub fn verify_message(
+    deps: Deps,
+    metadata: HexBinary,
+    message: HexBinary,
+) -> StdResult<VerifyResponse> {
+    let ism = message_ism(deps, &message)?;
 
-    let dispatch_msg = DispatchMsg {
-        dest_domain: source_domain,
-        recipient_addr: remote_contract_address.clone(),
-        msg_body: encode_settle_order_data(repayment_address, order_ids),
-        hook: Some(config.hook_addr.clone()),
-        metadata: None,
-    };
+    Ok(VerifyResponse {
+        verified: verify(deps, ism, metadata, message)?,
+    })
+}
+
+pub fn route_message(deps: Deps, message: HexBinary) -> StdResult<RouteResponse> {
+    Ok(RouteResponse {
+        ism: message_ism(deps, &message)?,
+    })
+}
+
+/// The override of the origin domain of `message`, then the gateway ISM, then
+/// the mailbox default.
+fn message_ism(deps: Deps, message: &HexBinary) -> StdResult<String> {
+    let origin = origin_domain(message)?;
+
+    if let Some(ism) = REMOTE_DOMAIN_ISMS.may_load(deps.storage, origin)? {
+        return Ok(ism.into_string());
+    }
+
+    if let Some(ism) = INTERCHAIN_SECURITY_MODULE.may_load(deps.storage)? {
+        return Ok(ism.into_string());
+    }
 
-    quote_dispatch(deps, config.mailbox_addr, dispatch_msg)
+    get_default_ism(deps, CONFIG.load(deps.storage)?.mailbox_addr)
 }
@@ -1,10 +1,14 @@
-use cosmwasm_std::{HexBinary, StdResult, Storage};
-use cw_storage_plus::{Item, Map};
-use go_fast::gateway::Config;
+use cosmwasm_std::{Addr, Empty, HexBinary, StdResult, Storage, Uint128};
+use cw_storage_plus::{Deque, Item, Map};
+use go_fast::gateway::{
+    Config, DomainSunset, DualAttestation, PendingAttestation, Proposal, QueuedSettlement,
+    RemoteAddressRotation, Role, SettlementDelay, Transport,
+};
 
 use crate::{
+    contract::{DEFAULT_ROTATION_GRACE_PERIOD_SECONDS, DEFAULT_TIMELOCK_DELAY_SECONDS},
     fills::Fills,
-    msg::{OrderStatus, SettlementDetails},
+    msg::{IbcTransferFallback, OrderStatus, SettlementDetails, SettlementWindow},
 };
 
 pub const NONCE: Item<u32> = Item::new("nonce");
@@ -12,16 +16,113 @@ pub const NONCE: Item<u32> = Item::new("nonce");
 pub const LOCAL_DOMAIN: Item<u32> = Item::new("local_domain");
 
 pub const REMOTE_DOMAINS: Map<u32, HexBinary> = Map::new("remote_domains");
+pub const DISABLED_REMOTE_DOMAINS: Map<u32, Empty> = Map::new("disabled_remote_domains");
+pub const REMOVED_REMOTE_DOMAINS: Map<u32, Empty> = Map::new("removed_remote_domains");
+pub const REMOTE_DOMAIN_ROTATIONS: Map<u32, RemoteAddressRotation> =
+    Map::new("remote_domain_rotations");
+pub const ROTATION_GRACE_PERIOD: Item<u64> = Item::new("rotation_grace_period");
+pub const REMOTE_DOMAIN_TRANSPORTS: Map<u32, Transport> = Map::new("remote_domain_transports");
+pub const REMOTE_DOMAIN_ATTESTATIONS: Map<u32, DualAttestation> =
+    Map::new("remote_domain_attestations");
+/// The remote domain using each IBC channel as its transport or attestation
+/// transport. A channel carries the commands of a single domain.
+pub const IBC_CHANNEL_DOMAINS: Map<&str, u32> = Map::new("ibc_channel_domains");
+pub const PENDING_ATTESTATIONS: Map<(u32, Vec<u8>), PendingAttestation> =
+    Map::new("pending_attestations");
+pub const DOMAIN_SUNSETS: Map<u32, DomainSunset> = Map::new("domain_sunsets");
+pub const INTERCHAIN_SECURITY_MODULE: Item<Addr> = Item::new("interchain_security_module");
+pub const REMOTE_DOMAIN_ISMS: Map<u32, Addr> = Map::new("remote_domain_isms");
+pub const SETTLEMENT_DELAYS: Map<u32, SettlementDelay> = Map::new("settlement_delays");
+pub const SETTLEMENT_WINDOWS: Map<u32, SettlementWindow> = Map::new("settlement_windows");
+pub const QUEUED_SETTLEMENT_COUNT: Item<u64> = Item::new("queued_settlement_count");
+pub const QUEUED_SETTLEMENTS: Map<u64, QueuedSettlement> = Map::new("queued_settlements");
+
+pub const ALLOWED_SUBMITTERS: Map<Addr, Empty> = Map::new("allowed_submitters");
+
+pub const ROLES: Map<(&str, &Addr), Role> = Map::new("roles");
+
+pub const PAUSED: Item<bool> = Item::new("paused");
+
+pub const TIMELOCK_DELAY: Item<u64> = Item::new("timelock_delay");
+pub const PROPOSAL_COUNT: Item<u64> = Item::new("proposal_count");
+pub const PROPOSALS: Map<u64, Proposal> = Map::new("proposals");
 
 pub const SETTLEMENT_DETAILS: Map<Vec<u8>, SettlementDetails> = Map::new("settlement_details");
 pub const ORDER_STATUSES: Map<Vec<u8>, OrderStatus> = Map::new("order_statuses");
 
 pub const CONFIG: Item<Config> = Item::new("config");
 
+/// Fallbacks of the IBC transfers sent in the current transaction, popped in
+/// order by the replies to their submessages.
+pub const PENDING_IBC_TRANSFERS: Deque<IbcTransferFallback> = Deque::new("pending_ibc_transfers");
+pub const IBC_TRANSFERS: Map<(String, u64), IbcTransferFallback> = Map::new("ibc_transfers");
+pub const CLAIMABLE_REFUNDS: Map<(&Addr, &str), Uint128> = Map::new("claimable_refunds");
+
 pub fn order_fills() -> Fills<'static> {
     Fills::new("fills", "filler_index")
 }
 
+/// Deployments instantiated before the timelock existed fall back to
+/// `DEFAULT_TIMELOCK_DELAY_SECONDS`.
+pub fn timelock_delay(storage: &dyn Storage) -> StdResult<u64> {
+    Ok(TIMELOCK_DELAY
+        .may_load(storage)?
+        .unwrap_or(DEFAULT_TIMELOCK_DELAY_SECONDS))
+}
+
+pub fn rotation_grace_period(storage: &dyn Storage) -> StdResult<u64> {
+    Ok(ROTATION_GRACE_PERIOD
+        .may_load(storage)?
+        .unwrap_or(DEFAULT_ROTATION_GRACE_PERIOD_SECONDS))
+}
+
+/// Domains without an explicit transport settle over Hyperlane.
+pub fn remote_domain_transport(storage: &dyn Storage, domain: u32) -> StdResult<Transport> {
+    Ok(REMOTE_DOMAIN_TRANSPORTS
+        .may_load(storage, domain)?
+        .unwrap_or_default())
+}
+
+/// The remote domain whose commands are carried over `channel_id`, either as
+/// its transport or its attestation transport, if any.
+pub fn ibc_channel_domain(storage: &dyn Storage, channel_id: &str) -> StdResult<Option<u32>> {
+    IBC_CHANNEL_DOMAINS.may_load(storage, channel_id)
+}
+
+/// Moves the channel index entry of one of `domain`'s transports from
+/// `previous` to `next`.
+pub fn update_ibc_channel_domain(
+    storage: &mut dyn Storage,
+    domain: u32,
+    previous: Option<&Transport>,
+    next: Option<&Transport>,
+) -> StdResult<()> {
+    if let Some(Transport::Ibc { channel_id }) = previous {
+        IBC_CHANNEL_DOMAINS.remove(storage, channel_id);
+    }
+
+    if let Some(Transport::Ibc { channel_id }) = next {
+        IBC_CHANNEL_DOMAINS.save(storage, channel_id, &domain)?;
+    }
+
+    Ok(())
+}
+
+pub fn next_proposal_id(storage: &mut dyn Storage) -> StdResult<u64> {
+    let id = PROPOSAL_COUNT.may_load(storage)?.unwrap_or_default() + 1;
+    PROPOSAL_COUNT.save(storage, &id)?;
+    Ok(id)
+}
+
+pub fn next_queued_settlement_id(storage: &mut dyn Storage) -> StdResult<u64> {
+    let id = QUEUED_SETTLEMENT_COUNT
+        .may_load(storage)?
+        .unwrap_or_default()
+        + 1;
+    QUEUED_SETTLEMENT_COUNT.save(storage, &id)?;
+    Ok(id)
+}
+
 pub fn next_nonce(storage: &mut dyn Storage) -> StdResult<u32> {
     let nonce = NONCE.load(storage)?;
     let new_nonce = nonce + 1;
@@ -0,0 +1,138 @@
+use cosmwasm_std::{Binary, Coin, CosmosMsg, StdError, StdResult};
+use serde_json::{Map, Value};
+
+use crate::error::{ContractError, ContractResult};
+
+pub const MSG_TRANSFER_TYPE_URL: &str = "/ibc.applications.transfer.v1.MsgTransfer";
+
+/// ICS-20 `MsgTransfer`, encoded by hand so the gateway does not need a
+/// protobuf dependency. Only the fields the gateway sets are supported.
+pub struct MsgTransfer {
+    pub source_port: String,
+    pub source_channel: String,
+    pub token: Coin,
+    pub sender: String,
+    pub receiver: String,
+    pub timeout_timestamp: u64,
+    pub memo: String,
+}
+
+impl MsgTransfer {
+    pub fn encode(&self) -> Vec<u8> {
+        let mut token = Vec::new();
+        encode_string(&mut token, 1, &self.token.denom);
+        encode_string(&mut token, 2, &self.token.amount.to_string());
+
+        let mut bz = Vec::new();
+        encode_string(&mut bz, 1, &self.source_port);
+        encode_string(&mut bz, 2, &self.source_channel);
+        encode_bytes(&mut bz, 3, &token);
+        encode_string(&mut bz, 4, &self.sender);
+        encode_string(&mut bz, 5, &self.receiver);
+        encode_varint_field(&mut bz, 7, self.timeout_timestamp);
+        encode_string(&mut bz, 8, &self.memo);
+        bz
+    }
+}
+
+impl From<MsgTransfer> for CosmosMsg {
+    fn from(msg: MsgTransfer) -> Self {
+        CosmosMsg::Stargate {
+            type_url: MSG_TRANSFER_TYPE_URL.to_string(),
+            value: Binary::from(msg.encode()),
+        }
+    }
+}
+
+/// Reads the packet sequence from an encoded `MsgTransferResponse`.
+pub fn decode_msg_transfer_response(bz: &[u8]) -> StdResult<u64> {
+    let mut pos = 0;
+    while pos < bz.len() {
+        let key = decode_varint(bz, &mut pos)?;
+        match key & 0x7 {
+            0 => {
+                let value = decode_varint(bz, &mut pos)?;
+                if key >> 3 == 1 {
+                    return Ok(value);
+                }
+            }
+            2 => {
+                let len = decode_varint(bz, &mut pos)? as usize;
+                pos += len;
+            }
+            _ => return Err(StdError::generic_err("invalid MsgTransferResponse")),
+        }
+    }
+
+    Err(StdError::generic_err(
+        "MsgTransferResponse missing sequence",
+    ))
+}
+
+/// Adds an ibc-hooks `ibc_callback` pointing at `callback` to a user supplied
+/// memo, which must be empty or a JSON object without an `ibc_callback` key.
+pub fn memo_with_callback(memo: Option<String>, callback: &str) -> ContractResult<String> {
+    let memo = memo.unwrap_or_default();
+
+    let mut fields = if memo.trim().is_empty() {
+        Map::new()
+    } else {
+        match serde_json::from_str(&memo) {
+            Ok(Value::Object(fields)) => fields,
+            _ => return Err(ContractError::InvalidIbcMemo),
+        }
+    };
+
+    if fields.contains_key("ibc_callback") {
+        return Err(ContractError::InvalidIbcMemo);
+    }
+
+    fields.insert("ibc_callback".to_string(), Value::from(callback));
+
+    serde_json::to_string(&fields).map_err(|_| ContractError::InvalidIbcMemo)
+}
+
+fn encode_varint(bz: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        bz.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    bz.push(value as u8);
+}
+
+fn encode_varint_field(bz: &mut Vec<u8>, field: u64, value: u64) {
+    if value == 0 {
+        return;
+    }
+    encode_varint(bz, field << 3);
+    encode_varint(bz, value);
+}
+
+fn encode_bytes(bz: &mut Vec<u8>, field: u64, value: &[u8]) {
+    if value.is_empty() {
+        return;
+    }
+    encode_varint(bz, (field << 3) | 2);
+    encode_varint(bz, value.len() as u64);
+    bz.extend_from_slice(value);
+}
+
+fn encode_string(bz: &mut Vec<u8>, field: u64, value: &str) {
+    encode_bytes(bz, field, value.as_bytes());
+}
+
+fn decode_varint(bz: &[u8], pos: &mut usize) -> StdResult<u64> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let byte = *bz
+            .get(*pos)
+            .ok_or_else(|| StdError::generic_err("unexpected end of protobuf varint"))?;
+        *pos += 1;
+        value |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+
+    Err(StdError::generic_err("protobuf varint overflow"))
+}
@@ -0,0 +1,283 @@
+use cosmwasm_std::{
+    to_json_binary, Coin, Deps, DepsMut, Env, Event, HexBinary, IbcMsg, IbcTimeout, Response,
+    StdError, StdResult, Storage, WasmMsg,
+};
+use go_fast::{
+    gateway::{DualAttestation, PendingAttestation, Transport},
+    helpers::keccak256_hash,
+};
+use hyperlane::mailbox::{quote_dispatch, DispatchMsg, ExecuteMsg as MailboxExecuteMsg};
+
+use crate::{
+    contract::IBC_SETTLEMENT_TIMEOUT_SECONDS,
+    error::{ContractError, ContractResponse, ContractResult},
+    execute::{refund_orders, settle_orders},
+    helpers::assert_remote_sender,
+    msg::Command,
+    state::{self, CONFIG, PENDING_ATTESTATIONS, REMOTE_DOMAIN_ATTESTATIONS},
+};
+
+/// Sends an encoded `Command` to the gateway at `recipient` on `domain` over
+/// the transport configured for that domain.
+pub fn dispatch_command(
+    deps: Deps,
+    env: &Env,
+    domain: u32,
+    recipient: HexBinary,
+    body: HexBinary,
+    funds: Vec<Coin>,
+) -> ContractResponse {
+    let transport = state::remote_domain_transport(deps.storage, domain)?;
+
+    dispatch_over(deps, env, transport, domain, recipient, body, funds)
+}
+
+/// Sends an encoded `Command` like `dispatch_command`, and also over the
+/// attestation transport of dual attested domains. The funds pay for the
+/// Hyperlane leg, at most one of the two transports can be Hyperlane.
+pub fn dispatch_attested_command(
+    deps: Deps,
+    env: &Env,
+    domain: u32,
+    recipient: HexBinary,
+    body: HexBinary,
+    funds: Vec<Coin>,
+) -> ContractResponse {
+    let Some(attestation) = REMOTE_DOMAIN_ATTESTATIONS.may_load(deps.storage, domain)? else {
+        return dispatch_command(deps, env, domain, recipient, body, funds);
+    };
+
+    let transport = state::remote_domain_transport(deps.storage, domain)?;
+
+    let (funds, attestation_funds) = if attestation.transport == Transport::Hyperlane {
+        (vec![], funds)
+    } else {
+        (funds, vec![])
+    };
+
+    let response = dispatch_over(
+        deps,
+        env,
+        transport,
+        domain,
+        recipient.clone(),
+        body.clone(),
+        funds,
+    )?;
+    let attestation_response = dispatch_over(
+        deps,
+        env,
+        attestation.transport,
+        domain,
+        recipient,
+        body,
+        attestation_funds,
+    )?;
+
+    Ok(response
+        .add_submessages(attestation_response.messages)
+        .add_events(attestation_response.events))
+}
+
+fn dispatch_over(
+    deps: Deps,
+    env: &Env,
+    transport: Transport,
+    domain: u32,
+    recipient: HexBinary,
+    body: HexBinary,
+    funds: Vec<Coin>,
+) -> ContractResponse {
+    match transport {
+        Transport::Hyperlane => {
+            let config = CONFIG.load(deps.storage)?;
+
+            let msg = WasmMsg::Execute {
+                contract_addr: config.mailbox_addr,
+                msg: to_json_binary(&MailboxExecuteMsg::Dispatch(DispatchMsg {
+                    dest_domain: domain,
+                    recipient_addr: recipient,
+                    msg_body: body,
+                    hook: Some(config.hook_addr),
+                    metadata: None,
+                }))?,
+                funds,
+            };
+
+            Ok(Response::new().add_message(msg))
+        }
+        Transport::Ibc { channel_id } => {
+            assert_no_funds(funds)?;
+
+            let msg = IbcMsg::SendPacket {
+                channel_id,
+                data: body.into(),
+                timeout: IbcTimeout::with_timestamp(
+                    env.block.time.plus_seconds(IBC_SETTLEMENT_TIMEOUT_SECONDS),
+                ),
+            };
+
+            Ok(Response::new().add_message(msg))
+        }
+        Transport::Mock { .. } => {
+            assert_no_funds(funds)?;
+
+            Ok(Response::new().add_event(
+                Event::new("mock_dispatch")
+                    .add_attribute("domain", domain.to_string())
+                    .add_attribute("recipient", recipient.to_hex())
+                    .add_attribute("body", body.to_hex()),
+            ))
+        }
+    }
+}
+
+/// Fee charged for dispatching a `Command` with `dispatch_attested_command`.
+/// Only Hyperlane charges for delivery.
+pub fn quote_dispatch_attested_command(
+    deps: Deps,
+    domain: u32,
+    recipient: HexBinary,
+    body: HexBinary,
+) -> StdResult<Vec<Coin>> {
+    let mut transports = vec![state::remote_domain_transport(deps.storage, domain)?];
+    if let Some(attestation) = REMOTE_DOMAIN_ATTESTATIONS.may_load(deps.storage, domain)? {
+        transports.push(attestation.transport);
+    }
+
+    if !transports.contains(&Transport::Hyperlane) {
+        return Ok(vec![]);
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+
+    quote_dispatch(
+        deps,
+        config.mailbox_addr,
+        DispatchMsg {
+            dest_domain: domain,
+            recipient_addr: recipient,
+            msg_body: body,
+            hook: Some(config.hook_addr),
+            metadata: None,
+        },
+    )
+}
+
+/// Whether a received command took effect, or is held back until a later
+/// message executes it: settlements waiting for their second attestation, or
+/// queued by the settlement delay of their domain.
+#[derive(Debug, PartialEq, Eq)]
+pub enum CommandStatus {
+    Executed,
+    Pending,
+}
+
+/// Executes a `Command` received from `origin`. `transport` is the transport
+/// it arrived over, which has to be one configured for `origin`, and `sender`
+/// has to be the remote gateway for `origin`. Commands from dual attested
+/// domains are held until they have arrived over both transports.
+pub fn receive_command(
+    deps: DepsMut,
+    env: Env,
+    transport: Transport,
+    origin: u32,
+    sender: &HexBinary,
+    body: HexBinary,
+) -> ContractResult<(Response, CommandStatus)> {
+    let attestation = REMOTE_DOMAIN_ATTESTATIONS.may_load(deps.storage, origin)?;
+
+    if state::remote_domain_transport(deps.storage, origin)? != transport
+        && attestation
+            .as_ref()
+            .is_none_or(|attestation| attestation.transport != transport)
+    {
+        return Err(ContractError::Unauthorized);
+    }
+
+    assert_remote_sender(deps.as_ref(), &env, origin, sender)?;
+
+    let command: Command = body.clone().try_into().map_err(StdError::generic_err)?;
+
+    if let Some(attestation) = attestation {
+        if let Some(pending) =
+            attest_command(deps.storage, &env, origin, &attestation, transport, &body)?
+        {
+            let action = match command {
+                Command::SettleOrders(_) => "settlement_pending_attestation",
+                Command::TimeoutOrders(_) => "timeout_pending_attestation",
+            };
+
+            let response = Response::new()
+                .add_attribute("action", action)
+                .add_attribute("payload_hash", pending.payload_hash.to_string())
+                .add_attribute("expires_at", pending.expires_at.seconds().to_string());
+
+            return Ok((response, CommandStatus::Pending));
+        }
+    }
+
+    match command {
+        Command::SettleOrders(settle_orders_message) => {
+            settle_orders(deps, &env, origin, settle_orders_message)
+        }
+        Command::TimeoutOrders(timeout_orders_message) => {
+            let response = refund_orders(deps, env, origin, timeout_orders_message)?;
+
+            Ok((response, CommandStatus::Executed))
+        }
+    }
+}
+
+/// Records that a command arrived over `transport`. Returns the pending
+/// attestation while it has only arrived over one of the domain's transports,
+/// and `None` once it has arrived over both.
+fn attest_command(
+    storage: &mut dyn Storage,
+    env: &Env,
+    origin: u32,
+    attestation: &DualAttestation,
+    transport: Transport,
+    body: &HexBinary,
+) -> ContractResult<Option<PendingAttestation>> {
+    let payload_hash = keccak256_hash(body);
+    let key = (origin, payload_hash.to_vec());
+
+    match PENDING_ATTESTATIONS.may_load(storage, key.clone())? {
+        Some(pending) if env.block.time < pending.expires_at => {
+            if pending.transport == transport {
+                return Ok(Some(pending));
+            }
+
+            PENDING_ATTESTATIONS.remove(storage, key);
+
+            Ok(None)
+        }
+        _ => {
+            let pending = PendingAttestation {
+                domain: origin,
+                payload_hash,
+                transport,
+                expires_at: env
+                    .block
+                    .time
+                    .plus_seconds(attestation.pending_expiry_seconds),
+            };
+
+            PENDING_ATTESTATIONS.save(storage, key, &pending)?;
+
+            Ok(Some(pending))
+        }
+    }
+}
+
+fn assert_no_funds(funds: Vec<Coin>) -> ContractResult<()> {
+    if !funds.is_empty() {
+        return Err(ContractError::UnexpectedFunds {
+            expected: vec![],
+            actual: funds,
+        });
+    }
+
+    Ok(())
+}
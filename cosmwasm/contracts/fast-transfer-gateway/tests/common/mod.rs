@@ -1,8 +1,8 @@
 use cosmwasm_std::{
     from_json,
-    testing::{mock_dependencies, mock_env, MockApi, MockQuerier},
+    testing::{mock_dependencies, mock_env, mock_info, MockApi, MockQuerier},
     to_json_binary, Addr, ContractResult, DepsMut, Env, HexBinary, MemoryStorage, MessageInfo,
-    OwnedDeps, QuerierResult, SystemResult, WasmQuery,
+    OwnedDeps, QuerierResult, SystemError, SystemResult, WasmQuery,
 };
 use go_fast::{
     gateway::{Config, ExecuteMsg},
@@ -14,7 +14,9 @@ use go_fast_transfer_cw::{
     helpers::bech32_encode,
     state::{CONFIG, LOCAL_DOMAIN, NONCE, REMOTE_DOMAINS},
 };
-use hyperlane::mailbox::{DefaultHookResponse, QueryMsg as HplQueryMsg, RequiredHookResponse};
+use hyperlane::mailbox::{
+    DefaultHookResponse, DefaultIsmResponse, QueryMsg as HplQueryMsg, RequiredHookResponse,
+};
 
 pub fn default_instantiate() -> (OwnedDeps<MemoryStorage, MockApi, MockQuerier>, Env) {
     let mut deps = mock_dependencies();
@@ -85,11 +87,21 @@ pub fn default_instantiate() -> (OwnedDeps<MemoryStorage, MockApi, MockQuerier>,
                                             .unwrap(),
                                         ));
                             }
+                            hyperlane::mailbox::MailboxQueryMsg::DefaultIsm {} => {
+                                return SystemResult::Ok(ContractResult::Ok(
+                                    to_json_binary(&DefaultIsmResponse {
+                                        default_ism: "default_ism".into(),
+                                    })
+                                    .unwrap(),
+                                ));
+                            }
                         },
                     }
                 }
 
-                panic!("Unsupported query: {:?}", query)
+                SystemResult::Err(SystemError::NoSuchContract {
+                    addr: contract_addr.clone(),
+                })
             }
             _ => panic!("Unsupported query: {:?}", query),
         }
@@ -100,6 +112,20 @@ pub fn default_instantiate() -> (OwnedDeps<MemoryStorage, MockApi, MockQuerier>,
     (deps, env)
 }
 
+/// Sender of messages delivered by the mailbox `default_instantiate`
+/// configures.
+pub fn mailbox_info() -> MessageInfo {
+    mock_info(
+        &bech32_encode(
+            "osmo",
+            &keccak256_hash("mailbox_contract_address".as_bytes()),
+        )
+        .unwrap()
+        .into_string(),
+        &[],
+    )
+}
+
 pub fn submit_order(
     deps: DepsMut,
     env: &Env,
@@ -114,6 +140,7 @@ pub fn submit_order(
         destination_domain: order.destination_domain,
         timeout_timestamp: order.timeout_timestamp,
         data: order.data.clone(),
+        refund_address: None,
     };
 
     go_fast_transfer_cw::contract::execute(deps, env.clone(), info.clone(), execute_msg)
@@ -0,0 +1,499 @@
+use crate::common::default_instantiate;
+use common::{mailbox_info, submit_order};
+use cosmwasm_std::{
+    coin, from_json, testing::mock_info, BankMsg, CosmosMsg, HexBinary, SubMsg, Uint128, WasmMsg,
+};
+use go_fast::{
+    gateway::{
+        DualAttestation, ExecuteMsg, PendingAttestation, QueryMsg, TimelockedAction, Transport,
+    },
+    helpers::keccak256_hash,
+    FastTransferOrder,
+};
+use go_fast_transfer_cw::{
+    helpers::{bech32_decode, left_pad_bytes},
+    msg::{OrderStatus, SettleOrdersMessage, TimeoutOrdersMessage},
+    state::{ORDER_STATUSES, REMOTE_DOMAINS, TIMELOCK_DELAY},
+};
+use hyperlane::message_recipient::HandleMsg;
+
+pub mod common;
+
+#[test]
+fn test_attestation_transport_must_be_independent() {
+    let (mut deps, env) = default_instantiate();
+
+    let owner = deps.api.with_prefix("osmo").addr_make("owner");
+
+    cw_ownable::initialize_owner(&mut deps.storage, &deps.api, Some(owner.as_str())).unwrap();
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env,
+        mock_info(owner.as_str(), &[]),
+        ExecuteMsg::ProposeAction {
+            action: TimelockedAction::SetRemoteDomainAttestation {
+                domain: 2,
+                attestation: Some(DualAttestation {
+                    transport: Transport::Hyperlane,
+                    pending_expiry_seconds: 100,
+                }),
+            },
+        },
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(
+        res,
+        "Attestation transport must differ from the domain transport"
+    );
+}
+
+#[test]
+fn test_settlement_requires_both_transports() {
+    let (mut deps, mut env) = default_instantiate();
+
+    let owner = deps.api.with_prefix("osmo").addr_make("owner");
+    let relayer = deps.api.with_prefix("osmo").addr_make("relayer");
+    let user_address = deps.api.with_prefix("osmo").addr_make("user");
+    let solver = deps.api.with_prefix("osmo").addr_make("solver");
+
+    cw_ownable::initialize_owner(&mut deps.storage, &deps.api, Some(owner.as_str())).unwrap();
+    TIMELOCK_DELAY.save(deps.as_mut().storage, &0).unwrap();
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner.as_str(), &[]),
+        ExecuteMsg::ProposeAction {
+            action: TimelockedAction::SetRemoteDomainAttestation {
+                domain: 2,
+                attestation: Some(DualAttestation {
+                    transport: Transport::Mock {
+                        relayer: relayer.to_string(),
+                    },
+                    pending_expiry_seconds: 100,
+                }),
+            },
+        },
+    )
+    .unwrap();
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner.as_str(), &[]),
+        ExecuteMsg::ExecuteProposal { id: 1 },
+    )
+    .unwrap();
+
+    let user_hex = HexBinary::from(left_pad_bytes(
+        bech32_decode(user_address.as_str()).unwrap(),
+        32,
+    ));
+
+    let order = FastTransferOrder {
+        sender: user_hex.clone(),
+        recipient: user_hex,
+        amount_in: Uint128::new(100_000_000),
+        amount_out: Uint128::new(98_000_000),
+        nonce: 1,
+        source_domain: 1,
+        destination_domain: 2,
+        timeout_timestamp: env.block.time.seconds() + 1000,
+        data: None,
+    };
+
+    for nonce in [1, 2] {
+        submit_order(
+            deps.as_mut(),
+            &env,
+            &mock_info(user_address.as_str(), &[coin(100_000_000, "uusdc")]),
+            &FastTransferOrder {
+                nonce,
+                ..order.clone()
+            },
+        )
+        .unwrap();
+    }
+
+    let remote_contract = REMOTE_DOMAINS.load(deps.as_ref().storage, 2).unwrap();
+    let repayment_address =
+        HexBinary::from(left_pad_bytes(bech32_decode(solver.as_str()).unwrap(), 32));
+    let body = SettleOrdersMessage {
+        order_ids: vec![order.id()],
+        repayment_address: repayment_address.clone(),
+    }
+    .encode();
+    let handle_msg = ExecuteMsg::Handle(HandleMsg {
+        origin: 2,
+        sender: remote_contract.clone(),
+        body: body.clone(),
+    });
+    let receive_msg = ExecuteMsg::ReceiveCommand {
+        origin: 2,
+        sender: remote_contract.clone(),
+        body: body.clone(),
+    };
+
+    for _ in 0..2 {
+        let res = go_fast_transfer_cw::contract::execute(
+            deps.as_mut(),
+            env.clone(),
+            mailbox_info(),
+            handle_msg.clone(),
+        )
+        .unwrap();
+
+        assert!(res.messages.is_empty());
+    }
+
+    let pending: Vec<PendingAttestation> = from_json(
+        go_fast_transfer_cw::contract::query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::PendingAttestations {
+                domain: 2,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        pending,
+        vec![PendingAttestation {
+            domain: 2,
+            payload_hash: keccak256_hash(&body),
+            transport: Transport::Hyperlane,
+            expires_at: env.block.time.plus_seconds(100),
+        }]
+    );
+    assert_eq!(
+        ORDER_STATUSES
+            .load(deps.as_ref().storage, order.id().to_vec())
+            .unwrap_or_default(),
+        OrderStatus::Unfilled
+    );
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(relayer.as_str(), &[]),
+        receive_msg,
+    )
+    .unwrap();
+
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: solver.to_string(),
+            amount: vec![coin(100_000_000, "uusdc")],
+        })]
+    );
+    assert_eq!(
+        ORDER_STATUSES
+            .load(deps.as_ref().storage, order.id().to_vec())
+            .unwrap(),
+        OrderStatus::Filled
+    );
+
+    // an attestation that expired has to start over
+    let second_order = FastTransferOrder { nonce: 2, ..order };
+    let body = SettleOrdersMessage {
+        order_ids: vec![second_order.id()],
+        repayment_address,
+    }
+    .encode();
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mailbox_info(),
+        ExecuteMsg::Handle(HandleMsg {
+            origin: 2,
+            sender: remote_contract.clone(),
+            body: body.clone(),
+        }),
+    )
+    .unwrap();
+
+    env.block.time = env.block.time.plus_seconds(100);
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(relayer.as_str(), &[]),
+        ExecuteMsg::ReceiveCommand {
+            origin: 2,
+            sender: remote_contract,
+            body,
+        },
+    )
+    .unwrap();
+
+    assert!(res.messages.is_empty());
+    assert_eq!(
+        ORDER_STATUSES
+            .load(deps.as_ref().storage, second_order.id().to_vec())
+            .unwrap_or_default(),
+        OrderStatus::Unfilled
+    );
+}
+
+#[test]
+fn test_timeout_requires_both_transports() {
+    let (mut deps, env) = default_instantiate();
+
+    let owner = deps.api.with_prefix("osmo").addr_make("owner");
+    let relayer = deps.api.with_prefix("osmo").addr_make("relayer");
+    let user_address = deps.api.with_prefix("osmo").addr_make("user");
+
+    cw_ownable::initialize_owner(&mut deps.storage, &deps.api, Some(owner.as_str())).unwrap();
+    TIMELOCK_DELAY.save(deps.as_mut().storage, &0).unwrap();
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner.as_str(), &[]),
+        ExecuteMsg::ProposeAction {
+            action: TimelockedAction::SetRemoteDomainAttestation {
+                domain: 2,
+                attestation: Some(DualAttestation {
+                    transport: Transport::Mock {
+                        relayer: relayer.to_string(),
+                    },
+                    pending_expiry_seconds: 100,
+                }),
+            },
+        },
+    )
+    .unwrap();
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner.as_str(), &[]),
+        ExecuteMsg::ExecuteProposal { id: 1 },
+    )
+    .unwrap();
+
+    let user_hex = HexBinary::from(left_pad_bytes(
+        bech32_decode(user_address.as_str()).unwrap(),
+        32,
+    ));
+
+    let order = FastTransferOrder {
+        sender: user_hex.clone(),
+        recipient: user_hex,
+        amount_in: Uint128::new(100_000_000),
+        amount_out: Uint128::new(98_000_000),
+        nonce: 1,
+        source_domain: 1,
+        destination_domain: 2,
+        timeout_timestamp: env.block.time.seconds() + 1000,
+        data: None,
+    };
+
+    submit_order(
+        deps.as_mut(),
+        &env,
+        &mock_info(user_address.as_str(), &[coin(100_000_000, "uusdc")]),
+        &order,
+    )
+    .unwrap();
+
+    let remote_contract = REMOTE_DOMAINS.load(deps.as_ref().storage, 2).unwrap();
+    let body = TimeoutOrdersMessage {
+        order_ids: vec![order.id()],
+    }
+    .encode();
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mailbox_info(),
+        ExecuteMsg::Handle(HandleMsg {
+            origin: 2,
+            sender: remote_contract.clone(),
+            body: body.clone(),
+        }),
+    )
+    .unwrap();
+
+    assert!(res.messages.is_empty());
+    assert_eq!(
+        ORDER_STATUSES
+            .load(deps.as_ref().storage, order.id().to_vec())
+            .unwrap_or_default(),
+        OrderStatus::Unfilled
+    );
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env,
+        mock_info(relayer.as_str(), &[]),
+        ExecuteMsg::ReceiveCommand {
+            origin: 2,
+            sender: remote_contract,
+            body,
+        },
+    )
+    .unwrap();
+
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: user_address.to_string(),
+            amount: vec![coin(100_000_000, "uusdc")],
+        })]
+    );
+    assert_eq!(
+        ORDER_STATUSES
+            .load(deps.as_ref().storage, order.id().to_vec())
+            .unwrap(),
+        OrderStatus::Refunded
+    );
+}
+
+#[test]
+fn test_settlement_dispatched_over_both_transports() {
+    let (mut deps, env) = default_instantiate();
+
+    let owner = deps.api.with_prefix("osmo").addr_make("owner");
+    let relayer = deps.api.with_prefix("osmo").addr_make("relayer");
+    let solver = deps.api.with_prefix("osmo").addr_make("solver");
+
+    cw_ownable::initialize_owner(&mut deps.storage, &deps.api, Some(owner.as_str())).unwrap();
+    TIMELOCK_DELAY.save(deps.as_mut().storage, &0).unwrap();
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner.as_str(), &[]),
+        ExecuteMsg::ProposeAction {
+            action: TimelockedAction::SetRemoteDomainAttestation {
+                domain: 2,
+                attestation: Some(DualAttestation {
+                    transport: Transport::Mock {
+                        relayer: relayer.to_string(),
+                    },
+                    pending_expiry_seconds: 100,
+                }),
+            },
+        },
+    )
+    .unwrap();
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner.as_str(), &[]),
+        ExecuteMsg::ExecuteProposal { id: 1 },
+    )
+    .unwrap();
+
+    let order_id = HexBinary::from(vec![1; 32]);
+
+    go_fast_transfer_cw::state::order_fills()
+        .create_order_fill(deps.as_mut().storage, order_id.clone(), solver.clone(), 2)
+        .unwrap();
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env,
+        mock_info(solver.as_str(), &[coin(10, "uhyp")]),
+        ExecuteMsg::InitiateSettlement {
+            order_ids: vec![order_id],
+            repayment_address: HexBinary::from(left_pad_bytes(
+                bech32_decode(solver.as_str()).unwrap(),
+                32,
+            )),
+        },
+    )
+    .unwrap();
+
+    assert_eq!(res.messages.len(), 1);
+    assert!(matches!(
+        &res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute { funds, .. }) if *funds == vec![coin(10, "uhyp")]
+    ));
+    assert_eq!(res.events.len(), 1);
+    assert_eq!(res.events[0].ty, "mock_dispatch");
+}
+
+#[test]
+fn test_timeout_dispatched_over_both_transports() {
+    let (mut deps, env) = default_instantiate();
+
+    let owner = deps.api.with_prefix("osmo").addr_make("owner");
+    let relayer = deps.api.with_prefix("osmo").addr_make("relayer");
+    let user_address = deps.api.with_prefix("osmo").addr_make("user");
+
+    cw_ownable::initialize_owner(&mut deps.storage, &deps.api, Some(owner.as_str())).unwrap();
+    TIMELOCK_DELAY.save(deps.as_mut().storage, &0).unwrap();
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner.as_str(), &[]),
+        ExecuteMsg::ProposeAction {
+            action: TimelockedAction::SetRemoteDomainAttestation {
+                domain: 2,
+                attestation: Some(DualAttestation {
+                    transport: Transport::Mock {
+                        relayer: relayer.to_string(),
+                    },
+                    pending_expiry_seconds: 100,
+                }),
+            },
+        },
+    )
+    .unwrap();
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner.as_str(), &[]),
+        ExecuteMsg::ExecuteProposal { id: 1 },
+    )
+    .unwrap();
+
+    let user_hex = HexBinary::from(left_pad_bytes(
+        bech32_decode(user_address.as_str()).unwrap(),
+        32,
+    ));
+
+    let order = FastTransferOrder {
+        sender: user_hex.clone(),
+        recipient: user_hex,
+        amount_in: Uint128::new(100_000_000),
+        amount_out: Uint128::new(98_000_000),
+        nonce: 1,
+        source_domain: 2,
+        destination_domain: 1,
+        timeout_timestamp: env.block.time.seconds() - 1,
+        data: None,
+    };
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env,
+        mock_info(user_address.as_str(), &[coin(10, "uhyp")]),
+        ExecuteMsg::InitiateTimeout {
+            orders: vec![order],
+        },
+    )
+    .unwrap();
+
+    assert_eq!(res.messages.len(), 1);
+    assert!(matches!(
+        &res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute { funds, .. }) if *funds == vec![coin(10, "uhyp")]
+    ));
+    assert_eq!(res.events.len(), 1);
+    assert_eq!(res.events[0].ty, "mock_dispatch");
+}
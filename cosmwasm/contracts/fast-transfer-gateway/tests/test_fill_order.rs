@@ -5,7 +5,8 @@ use cosmwasm_std::{
     Uint128, WasmMsg,
 };
 use go_fast::{
-    gateway::{ExecuteMsg, OrderFill, QueryMsg},
+    gateway::{ExecuteMsg, FillSettlementStatus, OrderFill, QueryMsg},
+    receiver::{FillAction, GoFastReceiveMsg, ReceiverExecuteMsg},
     FastTransferOrder,
 };
 use go_fast_transfer_cw::{
@@ -87,7 +88,8 @@ fn test_fill_order() {
         OrderFill {
             order_id: order.id(),
             filler: Addr::unchecked("solver"),
-            source_domain: 2
+            source_domain: 2,
+            settlement_status: FillSettlementStatus::Unsettled,
         }
     );
 }
@@ -165,7 +167,80 @@ fn test_fill_order_with_data() {
         OrderFill {
             order_id: order.id(),
             filler: Addr::unchecked("solver"),
-            source_domain: 2
+            source_domain: 2,
+            settlement_status: FillSettlementStatus::Unsettled,
+        }
+    );
+}
+
+#[test]
+fn test_fill_order_with_go_fast_receive() {
+    let (mut deps, env) = default_instantiate();
+
+    let user_address = deps.api.with_prefix("osmo").addr_make("user");
+
+    let test_payload = to_json_binary(&TestMsg {
+        test: "payload".to_string(),
+    })
+    .unwrap();
+
+    let order = FastTransferOrder {
+        sender: HexBinary::from(left_pad_bytes(
+            bech32_decode(user_address.as_str()).unwrap(),
+            32,
+        )),
+        recipient: HexBinary::from(left_pad_bytes(
+            bech32_decode(user_address.as_str()).unwrap(),
+            32,
+        )),
+        amount_in: Uint128::new(100_000_000),
+        amount_out: Uint128::new(98_000_000),
+        nonce: 1,
+        source_domain: 2,
+        destination_domain: 1,
+        timeout_timestamp: env.block.time.seconds() + 1000,
+        data: Some(HexBinary::from(
+            to_json_binary(&FillAction::GoFastReceive {
+                payload: test_payload.clone(),
+            })
+            .unwrap(),
+        )),
+    };
+
+    let execute_msg = ExecuteMsg::FillOrder {
+        filler: Addr::unchecked("solver"),
+        order: order.clone(),
+    };
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("solver", &[coin(order.amount_out.u128(), "uusdc")]),
+        execute_msg.clone(),
+    )
+    .unwrap();
+
+    // assert the recipient was called with the wrapped receive message
+    assert_eq!(
+        res.messages[0],
+        SubMsg {
+            id: 0,
+            msg: WasmMsg::Execute {
+                contract_addr: user_address.into(),
+                msg: to_json_binary(&ReceiverExecuteMsg::GoFastReceive(GoFastReceiveMsg {
+                    order_id: order.id(),
+                    sender: order.sender.clone(),
+                    source_domain: 2,
+                    amount: order.amount_out,
+                    denom: "uusdc".to_string(),
+                    payload: test_payload,
+                }))
+                .unwrap(),
+                funds: vec![coin(order.amount_out.u128(), "uusdc")],
+            }
+            .into(),
+            gas_limit: None,
+            reply_on: ReplyOn::Never,
         }
     );
 }
@@ -0,0 +1,605 @@
+use crate::common::default_instantiate;
+use common::submit_order;
+use cosmwasm_std::{
+    coin, from_json,
+    testing::{mock_ibc_channel_connect_ack, mock_ibc_channel_open_init, mock_info},
+    to_json_binary, Addr, BankMsg, Binary, Env, HexBinary, IbcAcknowledgement, IbcEndpoint,
+    IbcOrder, IbcPacket, IbcPacketAckMsg, IbcPacketReceiveMsg, IbcPacketTimeoutMsg, IbcTimeout,
+    SubMsg, Uint128,
+};
+use go_fast::{
+    gateway::{DualAttestation, ExecuteMsg, FillSettlementStatus, OrderFill, QueryMsg, Transport},
+    FastTransferOrder,
+};
+use go_fast_transfer_cw::{
+    contract::IBC_APP_VERSION,
+    helpers::{bech32_decode, bech32_encode, left_pad_bytes},
+    msg::{CommandAck, OrderStatus, SettleOrdersMessage},
+    state::{
+        IBC_CHANNEL_DOMAINS, ORDER_STATUSES, REMOTE_DOMAINS, REMOTE_DOMAIN_ATTESTATIONS,
+        REMOTE_DOMAIN_TRANSPORTS,
+    },
+};
+
+pub mod common;
+
+fn command_packet(env: &Env, src_port_id: &str, channel_id: &str, body: HexBinary) -> IbcPacket {
+    IbcPacket::new(
+        body,
+        IbcEndpoint {
+            port_id: src_port_id.to_string(),
+            channel_id: "channel-100".to_string(),
+        },
+        IbcEndpoint {
+            port_id: format!("wasm.{}", env.contract.address),
+            channel_id: channel_id.to_string(),
+        },
+        1,
+        IbcTimeout::with_timestamp(env.block.time.plus_seconds(100)),
+    )
+}
+
+fn order_fill(deps: cosmwasm_std::Deps, env: &Env, order_id: HexBinary) -> OrderFill {
+    from_json(
+        go_fast_transfer_cw::contract::query(deps, env.clone(), QueryMsg::OrderFill { order_id })
+            .unwrap(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_ibc_channel_handshake() {
+    let (mut deps, env) = default_instantiate();
+
+    let res = go_fast_transfer_cw::ibc::ibc_channel_open(
+        deps.as_mut(),
+        env.clone(),
+        mock_ibc_channel_open_init("channel-0", IbcOrder::Ordered, IBC_APP_VERSION),
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(res, "Gateway channels must be unordered");
+
+    let res = go_fast_transfer_cw::ibc::ibc_channel_open(
+        deps.as_mut(),
+        env.clone(),
+        mock_ibc_channel_open_init("channel-0", IbcOrder::Unordered, "ics20-1"),
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(res, "Invalid IBC channel version: ics20-1");
+
+    go_fast_transfer_cw::ibc::ibc_channel_open(
+        deps.as_mut(),
+        env.clone(),
+        mock_ibc_channel_open_init("channel-0", IbcOrder::Unordered, IBC_APP_VERSION),
+    )
+    .unwrap();
+
+    go_fast_transfer_cw::ibc::ibc_channel_connect(
+        deps.as_mut(),
+        env,
+        mock_ibc_channel_connect_ack("channel-0", IbcOrder::Unordered, IBC_APP_VERSION),
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_ibc_packet_receive_settles_orders() {
+    let (mut deps, env) = default_instantiate();
+
+    let user_address = deps.api.with_prefix("osmo").addr_make("user");
+
+    REMOTE_DOMAIN_TRANSPORTS
+        .save(
+            deps.as_mut().storage,
+            2,
+            &Transport::Ibc {
+                channel_id: "channel-0".to_string(),
+            },
+        )
+        .unwrap();
+    IBC_CHANNEL_DOMAINS
+        .save(deps.as_mut().storage, "channel-0", &2)
+        .unwrap();
+
+    let user_hex = HexBinary::from(left_pad_bytes(
+        bech32_decode(user_address.as_str()).unwrap(),
+        32,
+    ));
+
+    let order = FastTransferOrder {
+        sender: user_hex.clone(),
+        recipient: user_hex,
+        amount_in: Uint128::new(100_000_000),
+        amount_out: Uint128::new(98_000_000),
+        nonce: 1,
+        source_domain: 1,
+        destination_domain: 2,
+        timeout_timestamp: env.block.time.seconds() + 1000,
+        data: None,
+    };
+
+    submit_order(
+        deps.as_mut(),
+        &env,
+        &mock_info(user_address.as_str(), &[coin(100_000_000, "uusdc")]),
+        &order,
+    )
+    .unwrap();
+
+    let remote_contract = REMOTE_DOMAINS.load(deps.as_ref().storage, 2).unwrap();
+    let remote_port_id = format!(
+        "wasm.{}",
+        bech32_encode("neutron", &remote_contract[12..]).unwrap()
+    );
+
+    let impostor_port_id = format!(
+        "wasm.{}",
+        deps.api.with_prefix("osmo").addr_make("impostor")
+    );
+
+    let repayment_address = deps.api.with_prefix("osmo").addr_make("solver");
+    let body = SettleOrdersMessage {
+        order_ids: vec![order.id()],
+        repayment_address: HexBinary::from(left_pad_bytes(
+            bech32_decode(repayment_address.as_str()).unwrap(),
+            32,
+        )),
+    }
+    .encode();
+
+    let res = go_fast_transfer_cw::ibc::ibc_packet_receive(
+        deps.as_mut(),
+        env.clone(),
+        IbcPacketReceiveMsg::new(command_packet(
+            &env,
+            &remote_port_id,
+            "channel-9",
+            body.clone(),
+        )),
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(res, "No remote domain settles over channel channel-9");
+
+    let res = go_fast_transfer_cw::ibc::ibc_packet_receive(
+        deps.as_mut(),
+        env.clone(),
+        IbcPacketReceiveMsg::new(command_packet(
+            &env,
+            &impostor_port_id,
+            "channel-0",
+            body.clone(),
+        )),
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(res, "Unauthorized");
+
+    let res = go_fast_transfer_cw::ibc::ibc_packet_receive(
+        deps.as_mut(),
+        env.clone(),
+        IbcPacketReceiveMsg::new(command_packet(&env, &remote_port_id, "channel-0", body)),
+    )
+    .unwrap();
+
+    assert_eq!(
+        res.acknowledgement,
+        to_json_binary(&CommandAck::Result(Binary::default())).unwrap()
+    );
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: repayment_address.to_string(),
+            amount: vec![coin(100_000_000, "uusdc")],
+        })]
+    );
+    assert_eq!(
+        ORDER_STATUSES
+            .load(deps.as_ref().storage, order.id().to_vec())
+            .unwrap(),
+        OrderStatus::Filled
+    );
+}
+
+#[test]
+fn test_ibc_settlement_ack_and_timeout() {
+    let (mut deps, env) = default_instantiate();
+
+    let user_address = deps.api.with_prefix("osmo").addr_make("user");
+    let solver = deps.api.with_prefix("osmo").addr_make("solver");
+
+    REMOTE_DOMAIN_TRANSPORTS
+        .save(
+            deps.as_mut().storage,
+            2,
+            &Transport::Ibc {
+                channel_id: "channel-0".to_string(),
+            },
+        )
+        .unwrap();
+    IBC_CHANNEL_DOMAINS
+        .save(deps.as_mut().storage, "channel-0", &2)
+        .unwrap();
+
+    let user_hex = HexBinary::from(left_pad_bytes(
+        bech32_decode(user_address.as_str()).unwrap(),
+        32,
+    ));
+
+    let order = FastTransferOrder {
+        sender: user_hex.clone(),
+        recipient: user_hex,
+        amount_in: Uint128::new(100_000_000),
+        amount_out: Uint128::new(98_000_000),
+        nonce: 1,
+        source_domain: 2,
+        destination_domain: 1,
+        timeout_timestamp: env.block.time.seconds() + 1000,
+        data: None,
+    };
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(solver.as_str(), &[coin(98_000_000, "uusdc")]),
+        ExecuteMsg::FillOrder {
+            filler: solver.clone(),
+            order: order.clone(),
+        },
+    )
+    .unwrap();
+
+    let repayment_address =
+        HexBinary::from(left_pad_bytes(bech32_decode(solver.as_str()).unwrap(), 32));
+    let settle_msg = ExecuteMsg::InitiateSettlement {
+        order_ids: vec![order.id()],
+        repayment_address: repayment_address.clone(),
+    };
+    let packet = command_packet(
+        &env,
+        &format!("wasm.{}", env.contract.address),
+        "channel-0",
+        SettleOrdersMessage {
+            order_ids: vec![order.id()],
+            repayment_address,
+        }
+        .encode(),
+    );
+
+    let initiate_settlement = |deps: cosmwasm_std::DepsMut| {
+        go_fast_transfer_cw::contract::execute(
+            deps,
+            env.clone(),
+            mock_info(solver.as_str(), &[]),
+            settle_msg.clone(),
+        )
+        .map_err(|err| err.to_string())
+    };
+
+    initiate_settlement(deps.as_mut()).unwrap();
+
+    assert_eq!(
+        order_fill(deps.as_ref(), &env, order.id()).settlement_status,
+        FillSettlementStatus::Pending
+    );
+    assert_eq!(
+        initiate_settlement(deps.as_mut()).unwrap_err(),
+        "Settlement of the order fill is already pending"
+    );
+
+    go_fast_transfer_cw::ibc::ibc_packet_timeout(
+        deps.as_mut(),
+        env.clone(),
+        IbcPacketTimeoutMsg::new(packet.clone()),
+    )
+    .unwrap();
+
+    assert_eq!(
+        order_fill(deps.as_ref(), &env, order.id()).settlement_status,
+        FillSettlementStatus::Unsettled
+    );
+
+    initiate_settlement(deps.as_mut()).unwrap();
+
+    go_fast_transfer_cw::ibc::ibc_packet_ack(
+        deps.as_mut(),
+        env.clone(),
+        IbcPacketAckMsg::new(
+            IbcAcknowledgement::encode_json(&CommandAck::Error("rejected".to_string())).unwrap(),
+            packet.clone(),
+        ),
+    )
+    .unwrap();
+
+    assert_eq!(
+        order_fill(deps.as_ref(), &env, order.id()).settlement_status,
+        FillSettlementStatus::Unsettled
+    );
+
+    initiate_settlement(deps.as_mut()).unwrap();
+
+    let res = go_fast_transfer_cw::ibc::ibc_packet_ack(
+        deps.as_mut(),
+        env.clone(),
+        IbcPacketAckMsg::new(
+            IbcAcknowledgement::encode_json(&CommandAck::Result(Binary::default())).unwrap(),
+            packet,
+        ),
+    )
+    .unwrap();
+
+    assert_eq!(
+        res.attributes,
+        vec![
+            ("action", "ibc_command_ack".to_string()),
+            ("success", "true".to_string()),
+            ("order_id", order.id().to_string()),
+        ]
+    );
+    assert_eq!(
+        order_fill(deps.as_ref(), &env, order.id()),
+        OrderFill {
+            order_id: order.id(),
+            filler: Addr::unchecked(solver.as_str()),
+            source_domain: 2,
+            settlement_status: FillSettlementStatus::Settled,
+        }
+    );
+    assert_eq!(
+        initiate_settlement(deps.as_mut()).unwrap_err(),
+        "Order fill already settled"
+    );
+}
+
+#[test]
+fn test_ibc_settlement_pending_attestation_ack() {
+    let (mut deps, env) = default_instantiate();
+
+    let relayer = deps.api.with_prefix("osmo").addr_make("relayer");
+    let user_address = deps.api.with_prefix("osmo").addr_make("user");
+    let solver = deps.api.with_prefix("osmo").addr_make("solver");
+
+    REMOTE_DOMAIN_TRANSPORTS
+        .save(
+            deps.as_mut().storage,
+            2,
+            &Transport::Ibc {
+                channel_id: "channel-0".to_string(),
+            },
+        )
+        .unwrap();
+    IBC_CHANNEL_DOMAINS
+        .save(deps.as_mut().storage, "channel-0", &2)
+        .unwrap();
+    REMOTE_DOMAIN_ATTESTATIONS
+        .save(
+            deps.as_mut().storage,
+            2,
+            &DualAttestation {
+                transport: Transport::Mock {
+                    relayer: relayer.to_string(),
+                },
+                pending_expiry_seconds: 100,
+            },
+        )
+        .unwrap();
+
+    let user_hex = HexBinary::from(left_pad_bytes(
+        bech32_decode(user_address.as_str()).unwrap(),
+        32,
+    ));
+
+    let order = FastTransferOrder {
+        sender: user_hex.clone(),
+        recipient: user_hex,
+        amount_in: Uint128::new(100_000_000),
+        amount_out: Uint128::new(98_000_000),
+        nonce: 1,
+        source_domain: 1,
+        destination_domain: 2,
+        timeout_timestamp: env.block.time.seconds() + 1000,
+        data: None,
+    };
+
+    submit_order(
+        deps.as_mut(),
+        &env,
+        &mock_info(user_address.as_str(), &[coin(100_000_000, "uusdc")]),
+        &order,
+    )
+    .unwrap();
+
+    let remote_contract = REMOTE_DOMAINS.load(deps.as_ref().storage, 2).unwrap();
+    let remote_port_id = format!(
+        "wasm.{}",
+        bech32_encode("neutron", &remote_contract[12..]).unwrap()
+    );
+    let body = SettleOrdersMessage {
+        order_ids: vec![order.id()],
+        repayment_address: HexBinary::from(left_pad_bytes(
+            bech32_decode(solver.as_str()).unwrap(),
+            32,
+        )),
+    }
+    .encode();
+
+    let res = go_fast_transfer_cw::ibc::ibc_packet_receive(
+        deps.as_mut(),
+        env.clone(),
+        IbcPacketReceiveMsg::new(command_packet(
+            &env,
+            &remote_port_id,
+            "channel-0",
+            body.clone(),
+        )),
+    )
+    .unwrap();
+
+    // the settlement is not paid out until it arrives over the mock transport
+    assert_eq!(
+        res.acknowledgement,
+        to_json_binary(&CommandAck::Pending {}).unwrap()
+    );
+    assert!(res.messages.is_empty());
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env,
+        mock_info(relayer.as_str(), &[]),
+        ExecuteMsg::ReceiveCommand {
+            origin: 2,
+            sender: remote_contract,
+            body,
+        },
+    )
+    .unwrap();
+
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: solver.to_string(),
+            amount: vec![coin(100_000_000, "uusdc")],
+        })]
+    );
+}
+
+#[test]
+fn test_ibc_pending_ack_keeps_fills_in_flight() {
+    let (mut deps, env) = default_instantiate();
+
+    let user_address = deps.api.with_prefix("osmo").addr_make("user");
+    let solver = deps.api.with_prefix("osmo").addr_make("solver");
+
+    REMOTE_DOMAIN_TRANSPORTS
+        .save(
+            deps.as_mut().storage,
+            2,
+            &Transport::Ibc {
+                channel_id: "channel-0".to_string(),
+            },
+        )
+        .unwrap();
+
+    let user_hex = HexBinary::from(left_pad_bytes(
+        bech32_decode(user_address.as_str()).unwrap(),
+        32,
+    ));
+
+    let order = FastTransferOrder {
+        sender: user_hex.clone(),
+        recipient: user_hex,
+        amount_in: Uint128::new(100_000_000),
+        amount_out: Uint128::new(98_000_000),
+        nonce: 1,
+        source_domain: 2,
+        destination_domain: 1,
+        timeout_timestamp: env.block.time.seconds() + 1000,
+        data: None,
+    };
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(solver.as_str(), &[coin(98_000_000, "uusdc")]),
+        ExecuteMsg::FillOrder {
+            filler: solver.clone(),
+            order: order.clone(),
+        },
+    )
+    .unwrap();
+
+    let repayment_address =
+        HexBinary::from(left_pad_bytes(bech32_decode(solver.as_str()).unwrap(), 32));
+    let settle_msg = ExecuteMsg::InitiateSettlement {
+        order_ids: vec![order.id()],
+        repayment_address: repayment_address.clone(),
+    };
+    let packet = command_packet(
+        &env,
+        &format!("wasm.{}", env.contract.address),
+        "channel-0",
+        SettleOrdersMessage {
+            order_ids: vec![order.id()],
+            repayment_address,
+        }
+        .encode(),
+    );
+
+    let ack = |deps: cosmwasm_std::DepsMut, ack: CommandAck| {
+        go_fast_transfer_cw::ibc::ibc_packet_ack(
+            deps,
+            env.clone(),
+            IbcPacketAckMsg::new(
+                IbcAcknowledgement::encode_json(&ack).unwrap(),
+                packet.clone(),
+            ),
+        )
+        .unwrap()
+    };
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(solver.as_str(), &[]),
+        settle_msg.clone(),
+    )
+    .unwrap();
+
+    let res = ack(deps.as_mut(), CommandAck::Pending {});
+
+    assert_eq!(
+        res.attributes,
+        vec![
+            ("action", "ibc_command_ack".to_string()),
+            ("success", "false".to_string()),
+            ("order_id", order.id().to_string()),
+        ]
+    );
+    assert_eq!(
+        order_fill(deps.as_ref(), &env, order.id()).settlement_status,
+        FillSettlementStatus::Accepted
+    );
+
+    // the remote gateway holds the settlement, sending it again would queue
+    // it twice
+    let err = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(solver.as_str(), &[]),
+        settle_msg,
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(err, "Settlement of the order fill is already pending");
+
+    // only an error ack makes the fill retryable again
+    ack(deps.as_mut(), CommandAck::Error("rejected".to_string()));
+
+    assert_eq!(
+        order_fill(deps.as_ref(), &env, order.id()).settlement_status,
+        FillSettlementStatus::Unsettled
+    );
+
+    ack(deps.as_mut(), CommandAck::Result(Binary::default()));
+
+    // a late pending ack or timeout does not undo a settlement
+    ack(deps.as_mut(), CommandAck::Pending {});
+    go_fast_transfer_cw::ibc::ibc_packet_timeout(
+        deps.as_mut(),
+        env.clone(),
+        IbcPacketTimeoutMsg::new(packet.clone()),
+    )
+    .unwrap();
+
+    assert_eq!(
+        order_fill(deps.as_ref(), &env, order.id()).settlement_status,
+        FillSettlementStatus::Settled
+    );
+}
@@ -0,0 +1,266 @@
+use cosmwasm_std::{
+    coin, testing::mock_info, to_json_binary, Addr, BankMsg, Binary, CosmosMsg, HexBinary, Reply,
+    ReplyOn, SubMsg, SubMsgResponse, SubMsgResult, Uint128,
+};
+use go_fast::{
+    gateway::{ExecuteMsg, IbcLifecycleComplete, SudoMsg},
+    receiver::FillAction,
+    FastTransferOrder,
+};
+use go_fast_transfer_cw::{
+    contract::IBC_TRANSFER_REPLY_ID,
+    helpers::{bech32_decode, left_pad_bytes},
+    state::{IBC_TRANSFERS, PENDING_IBC_TRANSFERS},
+    transfer::{memo_with_callback, MsgTransfer, MSG_TRANSFER_TYPE_URL},
+};
+
+use crate::common::default_instantiate;
+
+pub mod common;
+
+fn ibc_transfer_order(user_hex: HexBinary, timeout_timestamp: u64) -> FastTransferOrder {
+    FastTransferOrder {
+        sender: user_hex.clone(),
+        recipient: user_hex,
+        amount_in: Uint128::new(100_000_000),
+        amount_out: Uint128::new(98_000_000),
+        nonce: 1,
+        source_domain: 2,
+        destination_domain: 1,
+        timeout_timestamp,
+        data: Some(HexBinary::from(
+            to_json_binary(&FillAction::IbcTransfer {
+                channel_id: "channel-0".to_string(),
+                receiver: "cosmos1receiver".to_string(),
+                timeout_seconds: 600,
+                memo: Some(r#"{"forward":{"receiver":"noble1receiver"}}"#.to_string()),
+            })
+            .unwrap(),
+        )),
+    }
+}
+
+#[test]
+fn test_fill_order_with_ibc_transfer() {
+    let (mut deps, env) = default_instantiate();
+
+    let user_address = deps.api.with_prefix("osmo").addr_make("user");
+    let user_hex = HexBinary::from(left_pad_bytes(
+        bech32_decode(user_address.as_str()).unwrap(),
+        32,
+    ));
+
+    let order = ibc_transfer_order(user_hex, env.block.time.seconds() + 1000);
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("solver", &[coin(order.amount_out.u128(), "uusdc")]),
+        ExecuteMsg::FillOrder {
+            filler: Addr::unchecked("solver"),
+            order: order.clone(),
+        },
+    )
+    .unwrap();
+
+    let expected_transfer = MsgTransfer {
+        source_port: "transfer".to_string(),
+        source_channel: "channel-0".to_string(),
+        token: coin(order.amount_out.u128(), "uusdc"),
+        sender: env.contract.address.to_string(),
+        receiver: "cosmos1receiver".to_string(),
+        timeout_timestamp: env.block.time.plus_seconds(600).nanos(),
+        memo: r#"{"forward":{"receiver":"noble1receiver"},"ibc_callback":"fast_transfer_gateway"}"#
+            .to_string(),
+    };
+
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(res.messages[0].id, IBC_TRANSFER_REPLY_ID);
+    assert_eq!(res.messages[0].reply_on, ReplyOn::Success);
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Stargate {
+            type_url: MSG_TRANSFER_TYPE_URL.to_string(),
+            value: Binary::from(expected_transfer.encode()),
+        }
+    );
+
+    let pending = PENDING_IBC_TRANSFERS
+        .front(deps.as_ref().storage)
+        .unwrap()
+        .unwrap();
+    assert_eq!(pending.order_id, order.id());
+    assert_eq!(pending.fallback_address, user_address);
+}
+
+#[test]
+fn test_ibc_transfer_timeout_falls_back_to_recipient() {
+    let (mut deps, env) = default_instantiate();
+
+    let user_address = deps.api.with_prefix("osmo").addr_make("user");
+    let user_hex = HexBinary::from(left_pad_bytes(
+        bech32_decode(user_address.as_str()).unwrap(),
+        32,
+    ));
+
+    let order = ibc_transfer_order(user_hex, env.block.time.seconds() + 1000);
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("solver", &[coin(order.amount_out.u128(), "uusdc")]),
+        ExecuteMsg::FillOrder {
+            filler: Addr::unchecked("solver"),
+            order: order.clone(),
+        },
+    )
+    .unwrap();
+
+    // MsgTransferResponse { sequence: 7 }
+    go_fast_transfer_cw::contract::reply(
+        deps.as_mut(),
+        env.clone(),
+        Reply {
+            id: IBC_TRANSFER_REPLY_ID,
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![],
+                data: Some(Binary::from(vec![0x08, 0x07])),
+            }),
+        },
+    )
+    .unwrap();
+
+    assert!(PENDING_IBC_TRANSFERS
+        .is_empty(deps.as_ref().storage)
+        .unwrap());
+    assert!(IBC_TRANSFERS.has(deps.as_ref().storage, ("channel-0".to_string(), 7)));
+
+    let res = go_fast_transfer_cw::contract::sudo(
+        deps.as_mut(),
+        env.clone(),
+        SudoMsg::IbcLifecycleComplete(IbcLifecycleComplete::IbcTimeout {
+            channel: "channel-0".to_string(),
+            sequence: 7,
+        }),
+    )
+    .unwrap();
+
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: user_address.into(),
+            amount: vec![coin(order.amount_out.u128(), "uusdc")],
+        })]
+    );
+    assert!(!IBC_TRANSFERS.has(deps.as_ref().storage, ("channel-0".to_string(), 7)));
+
+    // a late callback for the same packet is a no-op
+    let res = go_fast_transfer_cw::contract::sudo(
+        deps.as_mut(),
+        env,
+        SudoMsg::IbcLifecycleComplete(IbcLifecycleComplete::IbcAck {
+            channel: "channel-0".to_string(),
+            sequence: 7,
+            ack: "".to_string(),
+            success: false,
+        }),
+    )
+    .unwrap();
+
+    assert!(res.messages.is_empty());
+}
+
+#[test]
+fn test_memo_with_callback() {
+    assert_eq!(
+        memo_with_callback(None, "gateway").unwrap(),
+        r#"{"ibc_callback":"gateway"}"#
+    );
+    assert_eq!(
+        memo_with_callback(Some("{ }".to_string()), "gateway").unwrap(),
+        r#"{"ibc_callback":"gateway"}"#
+    );
+    assert_eq!(
+        memo_with_callback(Some("not json".to_string()), "gateway")
+            .unwrap_err()
+            .to_string(),
+        "Invalid IBC memo"
+    );
+    assert_eq!(
+        memo_with_callback(Some(r#"{"ibc_callback":"other"}"#.to_string()), "gateway")
+            .unwrap_err()
+            .to_string(),
+        "Invalid IBC memo"
+    );
+    assert_eq!(
+        memo_with_callback(
+            Some(r#"{"\u0069bc_callback":"other"}"#.to_string()),
+            "gateway"
+        )
+        .unwrap_err()
+        .to_string(),
+        "Invalid IBC memo"
+    );
+
+    // only JSON objects can carry the callback
+    for memo in [
+        r#"["ibc_callback"]"#,
+        r#""memo""#,
+        "1",
+        r#"{"a":1} {"b":2}"#,
+    ] {
+        assert_eq!(
+            memo_with_callback(Some(memo.to_string()), "gateway")
+                .unwrap_err()
+                .to_string(),
+            "Invalid IBC memo"
+        );
+    }
+
+    assert_eq!(
+        memo_with_callback(
+            Some(r#"{"note":"{\"ibc_callback\":1}"}"#.to_string()),
+            "gateway"
+        )
+        .unwrap(),
+        r#"{"ibc_callback":"gateway","note":"{\"ibc_callback\":1}"}"#
+    );
+}
+
+#[test]
+fn test_fill_order_fails_on_ibc_transfer_timeout_overflow() {
+    let (mut deps, env) = default_instantiate();
+
+    let user_address = deps.api.with_prefix("osmo").addr_make("user");
+    let user_hex = HexBinary::from(left_pad_bytes(
+        bech32_decode(user_address.as_str()).unwrap(),
+        32,
+    ));
+
+    let order = FastTransferOrder {
+        data: Some(HexBinary::from(
+            to_json_binary(&FillAction::IbcTransfer {
+                channel_id: "channel-0".to_string(),
+                receiver: "cosmos1receiver".to_string(),
+                timeout_seconds: u64::MAX,
+                memo: None,
+            })
+            .unwrap(),
+        )),
+        ..ibc_transfer_order(user_hex, env.block.time.seconds() + 1000)
+    };
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env,
+        mock_info("solver", &[coin(order.amount_out.u128(), "uusdc")]),
+        ExecuteMsg::FillOrder {
+            filler: Addr::unchecked("solver"),
+            order,
+        },
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(res, "IBC transfer timeout is out of range");
+}
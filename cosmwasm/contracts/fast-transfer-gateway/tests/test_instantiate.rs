@@ -3,7 +3,7 @@ use cosmwasm_std::{
     Addr,
 };
 use go_fast::gateway::InstantiateMsg;
-use go_fast_transfer_cw::state::{CONFIG, LOCAL_DOMAIN, NONCE};
+use go_fast_transfer_cw::state::{CONFIG, LOCAL_DOMAIN, NONCE, TIMELOCK_DELAY};
 
 pub mod common;
 
@@ -22,6 +22,7 @@ fn test_instantiate() {
         mailbox_addr: "mailbox_contract_address".into(),
         hook_addr: "hook_contract_address".into(),
         local_domain: 1,
+        timelock_delay_seconds: 3600,
     };
 
     go_fast_transfer_cw::contract::instantiate(deps.as_mut(), env, info, instantiate_msg.clone())
@@ -39,4 +40,7 @@ fn test_instantiate() {
 
     let nonce = NONCE.load(deps.as_ref().storage).unwrap();
     assert_eq!(nonce, 0);
+
+    let timelock_delay = TIMELOCK_DELAY.load(deps.as_ref().storage).unwrap();
+    assert_eq!(timelock_delay, 3600);
 }
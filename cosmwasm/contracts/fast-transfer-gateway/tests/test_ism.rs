@@ -0,0 +1,261 @@
+use crate::common::default_instantiate;
+use cosmwasm_std::{
+    from_json, testing::mock_info, to_json_binary, Addr, ContractResult, HexBinary, SystemError,
+    SystemResult, WasmQuery,
+};
+use go_fast::gateway::{ExecuteMsg, QueryMsg, RemoteDomain, TimelockedAction};
+use go_fast_transfer_cw::state::TIMELOCK_DELAY;
+use hyperlane::ism::{
+    ExpectedIsmQueryMsg, InterchainSecurityModuleResponse, IsmQueryMsg, IsmSpecifierQueryMsg,
+    IsmType, ModuleTypeResponse, RouteResponse, RoutingIsmQueryMsg, VerifyResponse,
+};
+
+pub mod common;
+
+/// A Hyperlane message header from `origin` with an empty body.
+fn message(origin: u32) -> HexBinary {
+    let mut message = vec![3];
+    message.extend(1u32.to_be_bytes());
+    message.extend(origin.to_be_bytes());
+    message.extend([0; 32]);
+    message.extend(1u32.to_be_bytes());
+    message.extend([0; 32]);
+
+    HexBinary::from(message)
+}
+
+fn set_ism(
+    deps: &mut cosmwasm_std::OwnedDeps<
+        cosmwasm_std::MemoryStorage,
+        cosmwasm_std::testing::MockApi,
+        cosmwasm_std::testing::MockQuerier,
+    >,
+    env: &cosmwasm_std::Env,
+    owner: &Addr,
+    action: TimelockedAction,
+) {
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner.as_str(), &[]),
+        ExecuteMsg::ProposeAction { action },
+    )
+    .unwrap();
+
+    let id = go_fast_transfer_cw::state::PROPOSAL_COUNT
+        .load(deps.as_ref().storage)
+        .unwrap();
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner.as_str(), &[]),
+        ExecuteMsg::ExecuteProposal { id },
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_ism_is_owner_managed() {
+    let (mut deps, env) = default_instantiate();
+
+    let owner = deps.api.with_prefix("osmo").addr_make("owner");
+    let not_owner = deps.api.with_prefix("osmo").addr_make("not_owner");
+
+    cw_ownable::initialize_owner(&mut deps.storage, &deps.api, Some(owner.as_str())).unwrap();
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(not_owner.as_str(), &[]),
+        ExecuteMsg::ProposeAction {
+            action: TimelockedAction::SetRemoteDomainIsm {
+                domain: 2,
+                ism: Some(not_owner.to_string()),
+            },
+        },
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(res, "Caller is not the contract's current owner");
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env,
+        mock_info(owner.as_str(), &[]),
+        ExecuteMsg::ProposeAction {
+            action: TimelockedAction::SetInterchainSecurityModule {
+                ism: Some("INVALID".to_string()),
+            },
+        },
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(res, "Generic error: Invalid input: address not normalized");
+}
+
+#[test]
+fn test_ism_specifier_and_routing() {
+    let (mut deps, env) = default_instantiate();
+
+    let owner = deps.api.with_prefix("osmo").addr_make("owner");
+    let gateway_ism = deps.api.with_prefix("osmo").addr_make("gateway_ism");
+    let domain_ism = deps.api.with_prefix("osmo").addr_make("domain_ism");
+
+    cw_ownable::initialize_owner(&mut deps.storage, &deps.api, Some(owner.as_str())).unwrap();
+    TIMELOCK_DELAY.save(deps.as_mut().storage, &0).unwrap();
+
+    let specifier_query = QueryMsg::IsmSpecifier(IsmSpecifierQueryMsg::InterchainSecurityModule());
+    let route_query = |origin| {
+        QueryMsg::RoutingIsm(RoutingIsmQueryMsg::Route {
+            message: message(origin),
+        })
+    };
+
+    let specified: InterchainSecurityModuleResponse = from_json(
+        go_fast_transfer_cw::contract::query(deps.as_ref(), env.clone(), specifier_query.clone())
+            .unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(specified.ism, None);
+
+    let route: RouteResponse = from_json(
+        go_fast_transfer_cw::contract::query(deps.as_ref(), env.clone(), route_query(2)).unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(route.ism, "default_ism");
+
+    set_ism(
+        &mut deps,
+        &env,
+        &owner,
+        TimelockedAction::SetInterchainSecurityModule {
+            ism: Some(gateway_ism.to_string()),
+        },
+    );
+
+    let specified: InterchainSecurityModuleResponse = from_json(
+        go_fast_transfer_cw::contract::query(deps.as_ref(), env.clone(), specifier_query.clone())
+            .unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(specified.ism, Some(gateway_ism.clone()));
+
+    set_ism(
+        &mut deps,
+        &env,
+        &owner,
+        TimelockedAction::SetRemoteDomainIsm {
+            domain: 2,
+            ism: Some(domain_ism.to_string()),
+        },
+    );
+
+    // with an override the gateway routes messages itself
+    let specified: InterchainSecurityModuleResponse = from_json(
+        go_fast_transfer_cw::contract::query(deps.as_ref(), env.clone(), specifier_query.clone())
+            .unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(specified.ism, Some(env.contract.address.clone()));
+
+    let module_type: ModuleTypeResponse = from_json(
+        go_fast_transfer_cw::contract::query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::Ism(IsmQueryMsg::ModuleType {}),
+        )
+        .unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(module_type.typ, IsmType::Routing);
+
+    let route: RouteResponse = from_json(
+        go_fast_transfer_cw::contract::query(deps.as_ref(), env.clone(), route_query(2)).unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(route.ism, domain_ism.to_string());
+
+    let route: RouteResponse = from_json(
+        go_fast_transfer_cw::contract::query(deps.as_ref(), env.clone(), route_query(3)).unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(route.ism, gateway_ism.to_string());
+
+    let remote_domains: Vec<RemoteDomain> = from_json(
+        go_fast_transfer_cw::contract::query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::RemoteDomains {},
+        )
+        .unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(remote_domains[0].ism, Some(domain_ism.clone()));
+
+    // only the domain ISM verifies messages
+    let verifying_ism = domain_ism.to_string();
+    deps.querier
+        .update_wasm(move |query: &WasmQuery| match query {
+            WasmQuery::Smart { contract_addr, msg } => {
+                let ExpectedIsmQueryMsg::Ism(IsmQueryMsg::Verify { .. }) = from_json(msg).unwrap()
+                else {
+                    panic!("Unsupported query: {:?}", query)
+                };
+
+                SystemResult::Ok(ContractResult::Ok(
+                    to_json_binary(&VerifyResponse {
+                        verified: *contract_addr == verifying_ism,
+                    })
+                    .unwrap(),
+                ))
+            }
+            _ => SystemResult::Err(SystemError::UnsupportedRequest {
+                kind: "non smart query".to_string(),
+            }),
+        });
+
+    for (origin, verified) in [(2, true), (3, false)] {
+        let res: VerifyResponse = from_json(
+            go_fast_transfer_cw::contract::query(
+                deps.as_ref(),
+                env.clone(),
+                QueryMsg::Ism(IsmQueryMsg::Verify {
+                    metadata: HexBinary::default(),
+                    message: message(origin),
+                }),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(res.verified, verified);
+    }
+
+    set_ism(
+        &mut deps,
+        &env,
+        &owner,
+        TimelockedAction::SetRemoteDomainIsm {
+            domain: 2,
+            ism: None,
+        },
+    );
+
+    let specified: InterchainSecurityModuleResponse = from_json(
+        go_fast_transfer_cw::contract::query(deps.as_ref(), env, specifier_query).unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(specified.ism, Some(gateway_ism));
+}
@@ -0,0 +1,357 @@
+use crate::common::default_instantiate;
+use common::submit_order;
+use cosmwasm_std::{coin, from_json, testing::mock_info, BankMsg, HexBinary, SubMsg, Uint128};
+use go_fast::{
+    gateway::{DomainSunset, ExecuteMsg, QueryMsg, Role},
+    FastTransferOrder,
+};
+use go_fast_transfer_cw::{
+    contract::SUNSET_RECLAIM_DELAY_SECONDS,
+    helpers::{bech32_decode, left_pad_bytes},
+    msg::OrderStatus,
+    state::{DOMAIN_SUNSETS, ORDER_STATUSES},
+};
+
+pub mod common;
+
+#[test]
+fn test_reclaim_orders_for_sunset_domain() {
+    let (mut deps, mut env) = default_instantiate();
+
+    let owner = deps.api.with_prefix("osmo").addr_make("owner");
+    let domain_manager = deps.api.with_prefix("osmo").addr_make("domain_manager");
+    let user_address = deps.api.with_prefix("osmo").addr_make("user");
+
+    cw_ownable::initialize_owner(&mut deps.storage, &deps.api, Some(owner.as_str())).unwrap();
+
+    let user_hex = HexBinary::from(left_pad_bytes(
+        bech32_decode(user_address.as_str()).unwrap(),
+        32,
+    ));
+
+    let order = FastTransferOrder {
+        sender: user_hex.clone(),
+        recipient: user_hex,
+        amount_in: Uint128::new(100_000_000),
+        amount_out: Uint128::new(98_000_000),
+        nonce: 1,
+        source_domain: 1,
+        destination_domain: 2,
+        timeout_timestamp: env.block.time.seconds() + 1000,
+        data: None,
+    };
+
+    submit_order(
+        deps.as_mut(),
+        &env,
+        &mock_info(user_address.as_str(), &[coin(100_000_000, "uusdc")]),
+        &order,
+    )
+    .unwrap();
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(domain_manager.as_str(), &[]),
+        ExecuteMsg::SunsetRemoteDomain { domain: 2 },
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(res, "Caller does not have the domain_manager role");
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner.as_str(), &[]),
+        ExecuteMsg::GrantRole {
+            role: Role::DomainManager,
+            address: domain_manager.to_string(),
+        },
+    )
+    .unwrap();
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(domain_manager.as_str(), &[]),
+        ExecuteMsg::SunsetRemoteDomain { domain: 2 },
+    )
+    .unwrap();
+
+    let reclaimable_at = env.block.time.plus_seconds(SUNSET_RECLAIM_DELAY_SECONDS);
+
+    let sunsets: Vec<DomainSunset> = from_json(
+        go_fast_transfer_cw::contract::query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::DomainSunsets {},
+        )
+        .unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        sunsets,
+        vec![DomainSunset {
+            domain: 2,
+            sunset_at: env.block.time,
+            reclaimable_at,
+            next_nonce: 2,
+        }]
+    );
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner.as_str(), &[]),
+        ExecuteMsg::SetRemoteDomainEnabled {
+            domain: 2,
+            enabled: true,
+        },
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(res, "Remote domain has been sunset");
+
+    let res = submit_order(
+        deps.as_mut(),
+        &env,
+        &mock_info(user_address.as_str(), &[coin(100_000_000, "uusdc")]),
+        &FastTransferOrder {
+            nonce: 2,
+            ..order.clone()
+        },
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(res, "Remote domain is disabled");
+
+    env.block.time = env.block.time.plus_seconds(1001);
+
+    let reclaim_msg = ExecuteMsg::ReclaimOrders {
+        orders: vec![order.clone()],
+    };
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("anyone", &[]),
+        reclaim_msg.clone(),
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(
+        res,
+        format!(
+            "Orders for the sunset domain are reclaimable from {}",
+            reclaimable_at.seconds()
+        )
+    );
+
+    env.block.time = reclaimable_at;
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("anyone", &[]),
+        reclaim_msg.clone(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: user_address.to_string(),
+            amount: vec![coin(100_000_000, "uusdc")],
+        })]
+    );
+
+    assert_eq!(
+        ORDER_STATUSES
+            .load(deps.as_ref().storage, order.id().to_vec())
+            .unwrap(),
+        OrderStatus::Refunded
+    );
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env,
+        mock_info("anyone", &[]),
+        reclaim_msg,
+    )
+    .unwrap();
+
+    assert!(res.messages.is_empty());
+}
+
+#[test]
+fn test_reclaim_orders_requires_sunset_and_timeout() {
+    let (mut deps, mut env) = default_instantiate();
+
+    let owner = deps.api.with_prefix("osmo").addr_make("owner");
+    let user_address = deps.api.with_prefix("osmo").addr_make("user");
+
+    cw_ownable::initialize_owner(&mut deps.storage, &deps.api, Some(owner.as_str())).unwrap();
+
+    let user_hex = HexBinary::from(left_pad_bytes(
+        bech32_decode(user_address.as_str()).unwrap(),
+        32,
+    ));
+
+    let order = FastTransferOrder {
+        sender: user_hex.clone(),
+        recipient: user_hex,
+        amount_in: Uint128::new(100_000_000),
+        amount_out: Uint128::new(98_000_000),
+        nonce: 1,
+        source_domain: 1,
+        destination_domain: 2,
+        timeout_timestamp: env.block.time.seconds() + SUNSET_RECLAIM_DELAY_SECONDS * 2,
+        data: None,
+    };
+
+    submit_order(
+        deps.as_mut(),
+        &env,
+        &mock_info(user_address.as_str(), &[coin(100_000_000, "uusdc")]),
+        &order,
+    )
+    .unwrap();
+
+    let reclaim_msg = ExecuteMsg::ReclaimOrders {
+        orders: vec![order.clone()],
+    };
+
+    let mut expired_env = env.clone();
+    expired_env.block.time = env
+        .block
+        .time
+        .plus_seconds(SUNSET_RECLAIM_DELAY_SECONDS * 2 + 1);
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        expired_env,
+        mock_info("anyone", &[]),
+        reclaim_msg.clone(),
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(res, "Destination domain has not been sunset");
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner.as_str(), &[]),
+        ExecuteMsg::SunsetRemoteDomain { domain: 2 },
+    )
+    .unwrap();
+
+    env.block.time = env.block.time.plus_seconds(SUNSET_RECLAIM_DELAY_SECONDS);
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env,
+        mock_info("anyone", &[]),
+        reclaim_msg,
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(res, "Order not timed out");
+}
+
+#[test]
+fn test_reclaim_orders_submitted_before_sunset_only() {
+    let (mut deps, env) = default_instantiate();
+
+    let user_address = deps.api.with_prefix("osmo").addr_make("user");
+
+    let user_hex = HexBinary::from(left_pad_bytes(
+        bech32_decode(user_address.as_str()).unwrap(),
+        32,
+    ));
+
+    let order = FastTransferOrder {
+        sender: user_hex.clone(),
+        recipient: user_hex,
+        amount_in: Uint128::new(100_000_000),
+        amount_out: Uint128::new(98_000_000),
+        nonce: 1,
+        source_domain: 1,
+        destination_domain: 2,
+        timeout_timestamp: env.block.time.seconds() + 1000,
+        data: None,
+    };
+
+    for nonce in [1, 2] {
+        submit_order(
+            deps.as_mut(),
+            &env,
+            &mock_info(user_address.as_str(), &[coin(100_000_000, "uusdc")]),
+            &FastTransferOrder {
+                nonce,
+                ..order.clone()
+            },
+        )
+        .unwrap();
+    }
+
+    // a sunset declared between the two orders
+    DOMAIN_SUNSETS
+        .save(
+            deps.as_mut().storage,
+            2,
+            &DomainSunset {
+                domain: 2,
+                sunset_at: env.block.time,
+                reclaimable_at: env.block.time,
+                next_nonce: 2,
+            },
+        )
+        .unwrap();
+
+    let mut expired_env = env.clone();
+    expired_env.block.time = env.block.time.plus_seconds(1001);
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        expired_env.clone(),
+        mock_info("anyone", &[]),
+        ExecuteMsg::ReclaimOrders {
+            orders: vec![FastTransferOrder {
+                nonce: 2,
+                ..order.clone()
+            }],
+        },
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(
+        res,
+        "Order was submitted after the destination domain was sunset"
+    );
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        expired_env,
+        mock_info("anyone", &[]),
+        ExecuteMsg::ReclaimOrders {
+            orders: vec![order],
+        },
+    )
+    .unwrap();
+
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: user_address.to_string(),
+            amount: vec![coin(100_000_000, "uusdc")],
+        })]
+    );
+}
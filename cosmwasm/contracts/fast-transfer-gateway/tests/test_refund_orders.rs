@@ -1,11 +1,22 @@
 use crate::common::default_instantiate;
 use common::submit_order;
-use cosmwasm_std::{coin, testing::mock_info, BankMsg, HexBinary, ReplyOn, SubMsg, Uint128};
-use go_fast::{gateway::ExecuteMsg, helpers::keccak256_hash, FastTransferOrder};
+use cosmwasm_std::{
+    coin, from_json, testing::mock_info, BankMsg, Binary, Coin, CosmosMsg, HexBinary, Reply,
+    ReplyOn, SubMsg, SubMsgResponse, SubMsgResult, Uint128,
+};
+use go_fast::{
+    gateway::{
+        ExecuteMsg, IbcLifecycleComplete, IbcOrigin, OrderStatusResponse, QueryMsg, SudoMsg,
+    },
+    helpers::keccak256_hash,
+    FastTransferOrder,
+};
 use go_fast_transfer_cw::{
-    helpers::{bech32_decode, bech32_encode, left_pad_bytes},
+    contract::{IBC_REFUND_TIMEOUT_SECONDS, IBC_TRANSFER_REPLY_ID},
+    helpers::{bech32_decode, bech32_encode, derive_intermediate_sender, left_pad_bytes},
     msg::{OrderStatus, TimeoutOrdersMessage},
     state::{ORDER_STATUSES, REMOTE_DOMAINS},
+    transfer::{MsgTransfer, MSG_TRANSFER_TYPE_URL},
 };
 use hyperlane::message_recipient::HandleMsg;
 
@@ -313,3 +324,260 @@ fn test_refund_orders_fails_if_orders_destination_domain_is_not_domain_message_o
 
     assert_eq!(res, "Incorrect domain for settlement");
 }
+
+#[test]
+fn test_refund_orders_returns_ibc_hook_orders_over_ibc() {
+    let (mut deps, env) = default_instantiate();
+
+    let ibc_origin = IbcOrigin {
+        channel_id: "channel-0".to_string(),
+        sender: "cosmos1sender".to_string(),
+    };
+
+    let intermediate_sender =
+        derive_intermediate_sender(&ibc_origin.channel_id, &ibc_origin.sender, "osmo").unwrap();
+
+    let user_address = deps.api.with_prefix("osmo").addr_make("user");
+    let user_hex = HexBinary::from(left_pad_bytes(
+        bech32_decode(user_address.as_str()).unwrap(),
+        32,
+    ));
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(
+            intermediate_sender.as_str(),
+            &[coin(Uint128::new(100_000_000).u128(), "uusdc")],
+        ),
+        ExecuteMsg::IbcHookSubmitOrder {
+            ibc_origin: ibc_origin.clone(),
+            recipient: user_hex,
+            amount_in: Uint128::new(100_000_000),
+            amount_out: Uint128::new(98_000_000),
+            destination_domain: 2,
+            timeout_timestamp: env.block.time.seconds() + 1000,
+            data: None,
+        },
+    )
+    .unwrap();
+
+    let order_id: HexBinary = res.data.unwrap().into();
+
+    let remote_contract = REMOTE_DOMAINS.load(deps.as_ref().storage, 2).unwrap();
+
+    let info = mock_info(
+        &bech32_encode(
+            "osmo",
+            &keccak256_hash("mailbox_contract_address".as_bytes()),
+        )
+        .unwrap()
+        .into_string(),
+        &[],
+    );
+
+    let execute_msg = ExecuteMsg::Handle(HandleMsg {
+        origin: 2,
+        sender: remote_contract,
+        body: TimeoutOrdersMessage {
+            order_ids: vec![order_id.clone()],
+        }
+        .encode(),
+    });
+
+    let res = go_fast_transfer_cw::contract::execute(deps.as_mut(), env.clone(), info, execute_msg)
+        .unwrap();
+
+    let expected_transfer = MsgTransfer {
+        source_port: "transfer".to_string(),
+        source_channel: ibc_origin.channel_id.clone(),
+        token: coin(100_000_000, "uusdc"),
+        sender: env.contract.address.to_string(),
+        receiver: ibc_origin.sender.clone(),
+        timeout_timestamp: env
+            .block
+            .time
+            .plus_seconds(IBC_REFUND_TIMEOUT_SECONDS)
+            .nanos(),
+        memo: r#"{"ibc_callback":"fast_transfer_gateway"}"#.to_string(),
+    };
+
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(res.messages[0].id, IBC_TRANSFER_REPLY_ID);
+    assert_eq!(res.messages[0].reply_on, ReplyOn::Success);
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Stargate {
+            type_url: MSG_TRANSFER_TYPE_URL.to_string(),
+            value: Binary::from(expected_transfer.encode()),
+        }
+    );
+
+    let status = ORDER_STATUSES
+        .load(deps.as_ref().storage, order_id.to_vec())
+        .unwrap();
+    assert_eq!(status, OrderStatus::Refunded);
+
+    // MsgTransferResponse { sequence: 3 }
+    go_fast_transfer_cw::contract::reply(
+        deps.as_mut(),
+        env.clone(),
+        Reply {
+            id: IBC_TRANSFER_REPLY_ID,
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![],
+                data: Some(Binary::from(vec![0x08, 0x03])),
+            }),
+        },
+    )
+    .unwrap();
+
+    let res = go_fast_transfer_cw::contract::sudo(
+        deps.as_mut(),
+        env.clone(),
+        SudoMsg::IbcLifecycleComplete(IbcLifecycleComplete::IbcTimeout {
+            channel: ibc_origin.channel_id,
+            sequence: 3,
+        }),
+    )
+    .unwrap();
+
+    // the origin sender has no account on this chain, so the refund is held
+    // for the intermediate sender to claim
+    assert!(res.messages.is_empty());
+
+    let claimable: Vec<Coin> = from_json(
+        go_fast_transfer_cw::contract::query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::ClaimableRefunds {
+                address: intermediate_sender.to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(claimable, vec![coin(100_000_000, "uusdc")]);
+
+    let claim_msg = ExecuteMsg::ClaimRefunds {
+        recipient: user_address.to_string(),
+    };
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(intermediate_sender.as_str(), &[]),
+        claim_msg.clone(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: user_address.to_string(),
+            amount: vec![coin(100_000_000, "uusdc")],
+        })]
+    );
+
+    let err = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env,
+        mock_info(intermediate_sender.as_str(), &[]),
+        claim_msg,
+    )
+    .unwrap_err()
+    .to_string();
+    assert_eq!(err, "No refunds to claim");
+}
+
+#[test]
+fn test_refund_orders_pays_refund_address() {
+    let (mut deps, env) = default_instantiate();
+
+    let user_address = deps.api.with_prefix("osmo").addr_make("user");
+    let refund_address = deps.api.with_prefix("osmo").addr_make("refund");
+
+    let user_hex = HexBinary::from(left_pad_bytes(
+        bech32_decode(user_address.as_str()).unwrap(),
+        32,
+    ));
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(
+            user_address.as_str(),
+            &[coin(Uint128::new(100_000_000).u128(), "uusdc")],
+        ),
+        ExecuteMsg::SubmitOrder {
+            sender: user_hex.clone(),
+            recipient: user_hex.clone(),
+            amount_in: Uint128::new(100_000_000),
+            amount_out: Uint128::new(98_000_000),
+            destination_domain: 2,
+            timeout_timestamp: env.block.time.seconds() + 1000,
+            data: None,
+            refund_address: Some(refund_address.to_string()),
+        },
+    )
+    .unwrap();
+
+    let order_id: HexBinary = res.data.unwrap().into();
+
+    let order_status: OrderStatusResponse = from_json(
+        go_fast_transfer_cw::contract::query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::OrderStatus {
+                order_id: order_id.clone(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        order_status,
+        OrderStatusResponse {
+            order_id: order_id.clone(),
+            status: OrderStatus::Unfilled,
+            sender: user_hex,
+            destination_domain: 2,
+            amount: Uint128::new(100_000_000),
+            refund_address: Some(refund_address.clone()),
+            ibc_origin: None,
+        }
+    );
+
+    let remote_contract = REMOTE_DOMAINS.load(deps.as_ref().storage, 2).unwrap();
+
+    let info = mock_info(
+        &bech32_encode(
+            "osmo",
+            &keccak256_hash("mailbox_contract_address".as_bytes()),
+        )
+        .unwrap()
+        .into_string(),
+        &[],
+    );
+
+    let execute_msg = ExecuteMsg::Handle(HandleMsg {
+        origin: 2,
+        sender: remote_contract,
+        body: TimeoutOrdersMessage {
+            order_ids: vec![order_id],
+        }
+        .encode(),
+    });
+
+    let res =
+        go_fast_transfer_cw::contract::execute(deps.as_mut(), env, info, execute_msg).unwrap();
+
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: refund_address.into(),
+            amount: vec![coin(100_000_000, "uusdc")],
+        })]
+    );
+}
@@ -0,0 +1,403 @@
+use crate::common::default_instantiate;
+use common::{mailbox_info, submit_order};
+use cosmwasm_std::{coin, from_json, testing::mock_info, Addr, HexBinary, Uint128};
+use go_fast::{
+    gateway::{
+        ExecuteMsg, QueryMsg, RemoteAddressRotation, RemoteDomain, TimelockedAction, Transport,
+    },
+    FastTransferOrder,
+};
+use go_fast_transfer_cw::{
+    contract::{DEFAULT_ROTATION_GRACE_PERIOD_SECONDS, DEFAULT_TIMELOCK_DELAY_SECONDS},
+    helpers::{bech32_decode, left_pad_bytes},
+    msg::{OrderStatus, SettleOrdersMessage},
+    state::{ORDER_STATUSES, REMOTE_DOMAINS},
+};
+use hyperlane::message_recipient::HandleMsg;
+
+pub mod common;
+
+fn settle_msg(sender: HexBinary, order_ids: Vec<HexBinary>) -> ExecuteMsg {
+    ExecuteMsg::Handle(HandleMsg {
+        origin: 2,
+        sender,
+        body: SettleOrdersMessage {
+            order_ids,
+            repayment_address: HexBinary::from(left_pad_bytes(vec![9; 20], 32)),
+        }
+        .encode(),
+    })
+}
+
+fn user_order(user_address: &Addr, nonce: u32, timeout_timestamp: u64) -> FastTransferOrder {
+    let user_hex = HexBinary::from(left_pad_bytes(
+        bech32_decode(user_address.as_str()).unwrap(),
+        32,
+    ));
+
+    FastTransferOrder {
+        sender: user_hex.clone(),
+        recipient: user_hex,
+        amount_in: Uint128::new(100_000_000),
+        amount_out: Uint128::new(98_000_000),
+        nonce,
+        source_domain: 1,
+        destination_domain: 2,
+        timeout_timestamp,
+        data: None,
+    }
+}
+
+#[test]
+fn test_previous_address_accepted_during_grace_period() {
+    let (mut deps, mut env) = default_instantiate();
+
+    let owner = deps.api.with_prefix("osmo").addr_make("owner");
+    let user_address = deps.api.with_prefix("osmo").addr_make("user");
+
+    cw_ownable::initialize_owner(&mut deps.storage, &deps.api, Some(owner.as_str())).unwrap();
+
+    let order_a = user_order(&user_address, 1, env.block.time.seconds() + 1_000_000_000);
+    let order_b = user_order(&user_address, 2, env.block.time.seconds() + 1_000_000_000);
+
+    for order in [&order_a, &order_b] {
+        submit_order(
+            deps.as_mut(),
+            &env,
+            &mock_info(user_address.as_str(), &[coin(100_000_000, "uusdc")]),
+            order,
+        )
+        .unwrap();
+    }
+
+    let previous_address = REMOTE_DOMAINS.load(deps.as_ref().storage, 2).unwrap();
+    let new_address = HexBinary::from(left_pad_bytes(vec![2; 20], 32));
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner.as_str(), &[]),
+        ExecuteMsg::ProposeAction {
+            action: TimelockedAction::SetRemoteDomain {
+                domain: 2,
+                address: new_address.clone(),
+            },
+        },
+    )
+    .unwrap();
+
+    env.block.time = env.block.time.plus_seconds(DEFAULT_TIMELOCK_DELAY_SECONDS);
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner.as_str(), &[]),
+        ExecuteMsg::ExecuteProposal { id: 1 },
+    )
+    .unwrap();
+
+    let expires_at = env
+        .block
+        .time
+        .plus_seconds(DEFAULT_ROTATION_GRACE_PERIOD_SECONDS);
+
+    let remote_domains: Vec<RemoteDomain> = from_json(
+        go_fast_transfer_cw::contract::query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::RemoteDomains {},
+        )
+        .unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        remote_domains,
+        vec![RemoteDomain {
+            domain: 2,
+            address: new_address.clone(),
+            enabled: true,
+            rotation: Some(RemoteAddressRotation {
+                previous_address: previous_address.clone(),
+                expires_at,
+            }),
+            transport: Transport::Hyperlane,
+            attestation: None,
+            settlement_delay: None,
+            ism: None,
+        }]
+    );
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mailbox_info(),
+        settle_msg(previous_address.clone(), vec![order_a.id()]),
+    )
+    .unwrap();
+
+    assert_eq!(
+        ORDER_STATUSES
+            .load(deps.as_ref().storage, order_a.id().to_vec())
+            .unwrap(),
+        OrderStatus::Filled
+    );
+
+    env.block.time = expires_at;
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mailbox_info(),
+        settle_msg(previous_address, vec![order_b.id()]),
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(res, "Unauthorized");
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env,
+        mailbox_info(),
+        settle_msg(new_address, vec![order_b.id()]),
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_disabled_domain_blocks_orders_but_allows_settlement() {
+    let (mut deps, env) = default_instantiate();
+
+    let owner = deps.api.with_prefix("osmo").addr_make("owner");
+    let user_address = deps.api.with_prefix("osmo").addr_make("user");
+
+    cw_ownable::initialize_owner(&mut deps.storage, &deps.api, Some(owner.as_str())).unwrap();
+
+    let order = user_order(&user_address, 1, env.block.time.seconds() + 1000);
+
+    submit_order(
+        deps.as_mut(),
+        &env,
+        &mock_info(user_address.as_str(), &[coin(100_000_000, "uusdc")]),
+        &order,
+    )
+    .unwrap();
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner.as_str(), &[]),
+        ExecuteMsg::SetRemoteDomainEnabled {
+            domain: 2,
+            enabled: false,
+        },
+    )
+    .unwrap();
+
+    let res = submit_order(
+        deps.as_mut(),
+        &env,
+        &mock_info(user_address.as_str(), &[coin(100_000_000, "uusdc")]),
+        &user_order(&user_address, 2, env.block.time.seconds() + 1000),
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(res, "Remote domain is disabled");
+
+    let incoming_order = FastTransferOrder {
+        source_domain: 2,
+        destination_domain: 1,
+        ..order.clone()
+    };
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("solver", &[coin(98_000_000, "uusdc")]),
+        ExecuteMsg::FillOrder {
+            filler: Addr::unchecked("solver"),
+            order: incoming_order,
+        },
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(res, "Remote domain is disabled");
+
+    let remote_contract = REMOTE_DOMAINS.load(deps.as_ref().storage, 2).unwrap();
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mailbox_info(),
+        settle_msg(remote_contract, vec![order.id()]),
+    )
+    .unwrap();
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner.as_str(), &[]),
+        ExecuteMsg::SetRemoteDomainEnabled {
+            domain: 2,
+            enabled: true,
+        },
+    )
+    .unwrap();
+
+    submit_order(
+        deps.as_mut(),
+        &env,
+        &mock_info(user_address.as_str(), &[coin(100_000_000, "uusdc")]),
+        &user_order(&user_address, 2, env.block.time.seconds() + 1000),
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_remove_remote_domain() {
+    let (mut deps, mut env) = default_instantiate();
+
+    let owner = deps.api.with_prefix("osmo").addr_make("owner");
+    let user_address = deps.api.with_prefix("osmo").addr_make("user");
+
+    cw_ownable::initialize_owner(&mut deps.storage, &deps.api, Some(owner.as_str())).unwrap();
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("not_owner", &[]),
+        ExecuteMsg::ProposeAction {
+            action: TimelockedAction::RemoveRemoteDomain { domain: 2 },
+        },
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(res, "Caller does not have the domain_manager role");
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner.as_str(), &[]),
+        ExecuteMsg::ProposeAction {
+            action: TimelockedAction::RemoveRemoteDomain { domain: 3 },
+        },
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(res, "Unknown remote domain");
+
+    let address = REMOTE_DOMAINS.load(deps.as_ref().storage, 2).unwrap();
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner.as_str(), &[]),
+        ExecuteMsg::ProposeAction {
+            action: TimelockedAction::RemoveRemoteDomain { domain: 2 },
+        },
+    )
+    .unwrap();
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner.as_str(), &[]),
+        ExecuteMsg::ExecuteProposal { id: 1 },
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert!(res.starts_with("Proposal not executable"), "{res}");
+
+    env.block.time = env.block.time.plus_seconds(DEFAULT_TIMELOCK_DELAY_SECONDS);
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner.as_str(), &[]),
+        ExecuteMsg::ExecuteProposal { id: 1 },
+    )
+    .unwrap();
+
+    let remote_domains: Vec<RemoteDomain> = from_json(
+        go_fast_transfer_cw::contract::query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::RemoteDomains {},
+        )
+        .unwrap(),
+    )
+    .unwrap();
+
+    assert!(remote_domains.is_empty());
+
+    let res = submit_order(
+        deps.as_mut(),
+        &env,
+        &mock_info(user_address.as_str(), &[coin(100_000_000, "uusdc")]),
+        &user_order(&user_address, 1, env.block.time.seconds() + 1000),
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(res, "Unknown remote domain");
+
+    // a removed domain only comes back through the timelock
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner.as_str(), &[]),
+        ExecuteMsg::AddRemoteDomain {
+            domain: 2,
+            address: address.clone(),
+        },
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(
+        res,
+        "Changing a remote domain address requires a timelock proposal"
+    );
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner.as_str(), &[]),
+        ExecuteMsg::ProposeAction {
+            action: TimelockedAction::SetRemoteDomain {
+                domain: 2,
+                address: address.clone(),
+            },
+        },
+    )
+    .unwrap();
+
+    env.block.time = env.block.time.plus_seconds(DEFAULT_TIMELOCK_DELAY_SECONDS);
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner.as_str(), &[]),
+        ExecuteMsg::ExecuteProposal { id: 2 },
+    )
+    .unwrap();
+
+    assert_eq!(
+        REMOTE_DOMAINS.load(deps.as_ref().storage, 2).unwrap(),
+        address
+    );
+
+    submit_order(
+        deps.as_mut(),
+        &env,
+        &mock_info(user_address.as_str(), &[coin(100_000_000, "uusdc")]),
+        &user_order(&user_address, 1, env.block.time.seconds() + 1000),
+    )
+    .unwrap();
+}
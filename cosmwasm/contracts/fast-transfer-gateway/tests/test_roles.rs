@@ -0,0 +1,413 @@
+use crate::common::default_instantiate;
+use cosmwasm_std::{coin, from_json, testing::mock_info, Addr, HexBinary, Uint128};
+use cw_ownable::{Action, Ownership};
+use go_fast::{
+    gateway::{
+        ConfigUpdate, ExecuteMsg, QueryMsg, Role, RoleGrant, SettlementDelay, TimelockedAction,
+    },
+    FastTransferOrder,
+};
+use go_fast_transfer_cw::helpers::{bech32_decode, left_pad_bytes};
+
+pub mod common;
+
+#[test]
+fn test_grant_and_revoke_domain_manager() {
+    let (mut deps, env) = default_instantiate();
+
+    let owner = deps.api.with_prefix("osmo").addr_make("owner");
+    let manager = deps.api.with_prefix("osmo").addr_make("manager");
+
+    cw_ownable::initialize_owner(&mut deps.storage, &deps.api, Some(owner.as_str())).unwrap();
+
+    let add_msg = ExecuteMsg::AddRemoteDomain {
+        domain: 3,
+        address: HexBinary::from(left_pad_bytes(vec![3; 20], 32)),
+    };
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(manager.as_str(), &[]),
+        add_msg.clone(),
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(res, "Caller does not have the domain_manager role");
+
+    let grant_msg = ExecuteMsg::GrantRole {
+        role: Role::DomainManager,
+        address: manager.to_string(),
+    };
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(manager.as_str(), &[]),
+        grant_msg.clone(),
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(res, "Caller is not the contract's current owner");
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner.as_str(), &[]),
+        grant_msg,
+    )
+    .unwrap();
+
+    let roles: Vec<RoleGrant> = from_json(
+        go_fast_transfer_cw::contract::query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::Roles {
+                role: Some(Role::DomainManager),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        roles,
+        vec![RoleGrant {
+            role: Role::DomainManager,
+            address: manager.clone(),
+        }]
+    );
+
+    let roles: Vec<RoleGrant> = from_json(
+        go_fast_transfer_cw::contract::query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::Roles {
+                role: Some(Role::Guardian),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+
+    assert!(roles.is_empty());
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(manager.as_str(), &[]),
+        add_msg.clone(),
+    )
+    .unwrap();
+
+    let remote_domain: HexBinary = from_json(
+        go_fast_transfer_cw::contract::query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::RemoteDomain { domain: 3 },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        remote_domain,
+        HexBinary::from(left_pad_bytes(vec![3; 20], 32))
+    );
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner.as_str(), &[]),
+        ExecuteMsg::RevokeRole {
+            role: Role::DomainManager,
+            address: manager.to_string(),
+        },
+    )
+    .unwrap();
+
+    let roles: Vec<RoleGrant> = from_json(
+        go_fast_transfer_cw::contract::query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::Roles { role: None },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+
+    assert!(roles.is_empty());
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env,
+        mock_info(manager.as_str(), &[]),
+        add_msg,
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(res, "Caller does not have the domain_manager role");
+}
+
+#[test]
+fn test_guardian_pauses_submissions_and_fills() {
+    let (mut deps, env) = default_instantiate();
+
+    let owner = deps.api.with_prefix("osmo").addr_make("owner");
+    let guardian = deps.api.with_prefix("osmo").addr_make("guardian");
+    let user_address = deps.api.with_prefix("osmo").addr_make("user");
+
+    cw_ownable::initialize_owner(&mut deps.storage, &deps.api, Some(owner.as_str())).unwrap();
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner.as_str(), &[]),
+        ExecuteMsg::GrantRole {
+            role: Role::Guardian,
+            address: guardian.to_string(),
+        },
+    )
+    .unwrap();
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(guardian.as_str(), &[]),
+        ExecuteMsg::Pause {},
+    )
+    .unwrap();
+
+    let paused: bool = from_json(
+        go_fast_transfer_cw::contract::query(deps.as_ref(), env.clone(), QueryMsg::Paused {})
+            .unwrap(),
+    )
+    .unwrap();
+
+    assert!(paused);
+
+    let user_hex = HexBinary::from(left_pad_bytes(
+        bech32_decode(user_address.as_str()).unwrap(),
+        32,
+    ));
+
+    let order = FastTransferOrder {
+        sender: user_hex.clone(),
+        recipient: user_hex,
+        amount_in: Uint128::new(100_000_000),
+        amount_out: Uint128::new(98_000_000),
+        nonce: 1,
+        source_domain: 2,
+        destination_domain: 1,
+        timeout_timestamp: env.block.time.seconds() + 1000,
+        data: None,
+    };
+
+    let res = common::submit_order(
+        deps.as_mut(),
+        &env,
+        &mock_info(user_address.as_str(), &[coin(100_000_000, "uusdc")]),
+        &FastTransferOrder {
+            destination_domain: 2,
+            ..order.clone()
+        },
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(res, "Contract is paused");
+
+    let fill_msg = ExecuteMsg::FillOrder {
+        filler: Addr::unchecked("solver"),
+        order,
+    };
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("solver", &[coin(98_000_000, "uusdc")]),
+        fill_msg.clone(),
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(res, "Contract is paused");
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(guardian.as_str(), &[]),
+        ExecuteMsg::Unpause {},
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(res, "Caller is not the contract's current owner");
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner.as_str(), &[]),
+        ExecuteMsg::Unpause {},
+    )
+    .unwrap();
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env,
+        mock_info("solver", &[coin(98_000_000, "uusdc")]),
+        fill_msg,
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_two_step_ownership_transfer() {
+    let (mut deps, env) = default_instantiate();
+
+    let owner = deps.api.with_prefix("osmo").addr_make("owner");
+    let new_owner = deps.api.with_prefix("osmo").addr_make("new_owner");
+
+    cw_ownable::initialize_owner(&mut deps.storage, &deps.api, Some(owner.as_str())).unwrap();
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner.as_str(), &[]),
+        ExecuteMsg::UpdateOwnership(Action::TransferOwnership {
+            new_owner: new_owner.to_string(),
+            expiry: None,
+        }),
+    )
+    .unwrap();
+
+    let grant_msg = ExecuteMsg::GrantRole {
+        role: Role::Guardian,
+        address: new_owner.to_string(),
+    };
+
+    // the pending owner has no rights until they accept
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(new_owner.as_str(), &[]),
+        grant_msg.clone(),
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(res, "Caller is not the contract's current owner");
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(new_owner.as_str(), &[]),
+        ExecuteMsg::UpdateOwnership(Action::AcceptOwnership),
+    )
+    .unwrap();
+
+    let ownership: Ownership<Addr> = from_json(
+        go_fast_transfer_cw::contract::query(deps.as_ref(), env.clone(), QueryMsg::Ownership {})
+            .unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(ownership.owner, Some(new_owner.clone()));
+    assert_eq!(ownership.pending_owner, None);
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env,
+        mock_info(new_owner.as_str(), &[]),
+        grant_msg,
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_rate_limit_and_fee_managers_propose_their_actions() {
+    let (mut deps, env) = default_instantiate();
+
+    let owner = deps.api.with_prefix("osmo").addr_make("owner");
+    let domain_manager = deps.api.with_prefix("osmo").addr_make("domain_manager");
+    let rate_limit_manager = deps.api.with_prefix("osmo").addr_make("rate_limit_manager");
+    let fee_manager = deps.api.with_prefix("osmo").addr_make("fee_manager");
+
+    cw_ownable::initialize_owner(&mut deps.storage, &deps.api, Some(owner.as_str())).unwrap();
+
+    for (role, address) in [
+        (Role::DomainManager, &domain_manager),
+        (Role::RateLimitManager, &rate_limit_manager),
+        (Role::FeeManager, &fee_manager),
+    ] {
+        go_fast_transfer_cw::contract::execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(owner.as_str(), &[]),
+            ExecuteMsg::GrantRole {
+                role,
+                address: address.to_string(),
+            },
+        )
+        .unwrap();
+    }
+
+    let propose = |action: TimelockedAction| ExecuteMsg::ProposeAction { action };
+
+    let set_settlement_delay = propose(TimelockedAction::SetSettlementDelay {
+        domain: 2,
+        settlement_delay: Some(SettlementDelay {
+            threshold: Uint128::new(1_000_000),
+            delay_seconds: 3600,
+        }),
+    });
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(domain_manager.as_str(), &[]),
+        set_settlement_delay.clone(),
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(res, "Caller does not have the rate_limit_manager role");
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(rate_limit_manager.as_str(), &[]),
+        set_settlement_delay,
+    )
+    .unwrap();
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(fee_manager.as_str(), &[]),
+        propose(TimelockedAction::UpdateConfig(ConfigUpdate {
+            hook_addr: Some("new_hook".to_string()),
+            ..ConfigUpdate::default()
+        })),
+    )
+    .unwrap();
+
+    // any other config change is left to the owner
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env,
+        mock_info(fee_manager.as_str(), &[]),
+        propose(TimelockedAction::UpdateConfig(ConfigUpdate {
+            hook_addr: Some("new_hook".to_string()),
+            mailbox_addr: Some("new_mailbox".to_string()),
+            ..ConfigUpdate::default()
+        })),
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(res, "Caller is not the contract's current owner");
+}
@@ -0,0 +1,395 @@
+use crate::common::default_instantiate;
+use common::{mailbox_info, submit_order};
+use cosmwasm_std::{
+    coin, from_json,
+    testing::{mock_info, MockApi, MockQuerier},
+    BankMsg, Env, Event, HexBinary, MemoryStorage, OwnedDeps, SubMsg, Uint128,
+};
+use go_fast::{
+    gateway::{
+        ExecuteMsg, QueryMsg, QueuedSettlement, Role, SettlementDelay, TimelockedAction, Transport,
+    },
+    FastTransferOrder,
+};
+use go_fast_transfer_cw::{
+    helpers::{bech32_decode, left_pad_bytes},
+    msg::{OrderStatus, SettleOrdersMessage},
+    state::{ORDER_STATUSES, REMOTE_DOMAINS, TIMELOCK_DELAY},
+    transport::{receive_command, CommandStatus},
+};
+use hyperlane::message_recipient::HandleMsg;
+
+pub mod common;
+
+/// Sets a 100_000_000 uusdc threshold with a one hour delay on domain 2 and
+/// submits one order of 100_000_000 uusdc per nonce to it.
+fn setup(
+    deps: &mut OwnedDeps<MemoryStorage, MockApi, MockQuerier>,
+    env: &Env,
+    nonces: &[u32],
+) -> Vec<FastTransferOrder> {
+    let owner = deps.api.with_prefix("osmo").addr_make("owner");
+    let user_address = deps.api.with_prefix("osmo").addr_make("user");
+
+    cw_ownable::initialize_owner(&mut deps.storage, &deps.api, Some(owner.as_str())).unwrap();
+    TIMELOCK_DELAY.save(deps.as_mut().storage, &0).unwrap();
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner.as_str(), &[]),
+        ExecuteMsg::ProposeAction {
+            action: TimelockedAction::SetSettlementDelay {
+                domain: 2,
+                settlement_delay: Some(SettlementDelay {
+                    threshold: Uint128::new(100_000_000),
+                    delay_seconds: 3600,
+                }),
+            },
+        },
+    )
+    .unwrap();
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner.as_str(), &[]),
+        ExecuteMsg::ExecuteProposal { id: 1 },
+    )
+    .unwrap();
+
+    let user_hex = HexBinary::from(left_pad_bytes(
+        bech32_decode(user_address.as_str()).unwrap(),
+        32,
+    ));
+
+    nonces
+        .iter()
+        .map(|nonce| {
+            let order = FastTransferOrder {
+                sender: user_hex.clone(),
+                recipient: user_hex.clone(),
+                amount_in: Uint128::new(100_000_000),
+                amount_out: Uint128::new(98_000_000),
+                nonce: *nonce,
+                source_domain: 1,
+                destination_domain: 2,
+                timeout_timestamp: env.block.time.seconds() + 1000,
+                data: None,
+            };
+
+            submit_order(
+                deps.as_mut(),
+                env,
+                &mock_info(user_address.as_str(), &[coin(100_000_000, "uusdc")]),
+                &order,
+            )
+            .unwrap();
+
+            order
+        })
+        .collect()
+}
+
+fn handle_settlement(
+    deps: &mut OwnedDeps<MemoryStorage, MockApi, MockQuerier>,
+    env: &Env,
+    order_ids: Vec<HexBinary>,
+    repayment_address: HexBinary,
+) -> cosmwasm_std::Response {
+    let remote_contract = REMOTE_DOMAINS.load(deps.as_ref().storage, 2).unwrap();
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mailbox_info(),
+        ExecuteMsg::Handle(HandleMsg {
+            origin: 2,
+            sender: remote_contract,
+            body: SettleOrdersMessage {
+                order_ids,
+                repayment_address,
+            }
+            .encode(),
+        }),
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_settlement_below_threshold_pays_immediately() {
+    let (mut deps, env) = default_instantiate();
+
+    let solver = deps.api.with_prefix("osmo").addr_make("solver");
+
+    let orders = setup(&mut deps, &env, &[1]);
+
+    let res = handle_settlement(
+        &mut deps,
+        &env,
+        vec![orders[0].id()],
+        HexBinary::from(left_pad_bytes(bech32_decode(solver.as_str()).unwrap(), 32)),
+    );
+
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: solver.to_string(),
+            amount: vec![coin(100_000_000, "uusdc")],
+        })]
+    );
+    assert_eq!(
+        ORDER_STATUSES
+            .load(deps.as_ref().storage, orders[0].id().to_vec())
+            .unwrap(),
+        OrderStatus::Filled
+    );
+}
+
+#[test]
+fn test_large_settlement_is_queued_until_delay_passes() {
+    let (mut deps, mut env) = default_instantiate();
+
+    let solver = deps.api.with_prefix("osmo").addr_make("solver");
+
+    let orders = setup(&mut deps, &env, &[1, 2]);
+    let order_ids: Vec<HexBinary> = orders.iter().map(|order| order.id()).collect();
+    let repayment_address =
+        HexBinary::from(left_pad_bytes(bech32_decode(solver.as_str()).unwrap(), 32));
+
+    let res = handle_settlement(
+        &mut deps,
+        &env,
+        order_ids.clone(),
+        repayment_address.clone(),
+    );
+
+    let executable_at = env.block.time.plus_seconds(3600);
+
+    assert!(res.messages.is_empty());
+    assert_eq!(
+        res.events,
+        vec![Event::new("settlement_queued")
+            .add_attribute("id", "1")
+            .add_attribute("domain", "2")
+            .add_attribute("amount", "200000000")
+            .add_attribute("executable_at", executable_at.seconds().to_string())]
+    );
+
+    let queued: Vec<QueuedSettlement> = from_json(
+        go_fast_transfer_cw::contract::query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::QueuedSettlements {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        queued,
+        vec![QueuedSettlement {
+            id: 1,
+            domain: 2,
+            order_ids: order_ids.clone(),
+            repayment_address,
+            amount: Uint128::new(200_000_000),
+            executable_at,
+        }]
+    );
+    for order_id in &order_ids {
+        assert_eq!(
+            ORDER_STATUSES
+                .load(deps.as_ref().storage, order_id.to_vec())
+                .unwrap_or_default(),
+            OrderStatus::Unfilled
+        );
+    }
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("anyone", &[]),
+        ExecuteMsg::ExecuteQueuedSettlement { id: 1 },
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(
+        res,
+        format!(
+            "Queued settlement not executable until {}",
+            executable_at.seconds()
+        )
+    );
+
+    env.block.time = executable_at;
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("anyone", &[]),
+        ExecuteMsg::ExecuteQueuedSettlement { id: 1 },
+    )
+    .unwrap();
+
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: solver.to_string(),
+            amount: vec![coin(200_000_000, "uusdc")],
+        })]
+    );
+    for order_id in &order_ids {
+        assert_eq!(
+            ORDER_STATUSES
+                .load(deps.as_ref().storage, order_id.to_vec())
+                .unwrap(),
+            OrderStatus::Filled
+        );
+    }
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env,
+        mock_info("anyone", &[]),
+        ExecuteMsg::ExecuteQueuedSettlement { id: 1 },
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(res, "Queued settlement 1 not found");
+}
+
+#[test]
+fn test_guardian_vetoes_queued_settlement() {
+    let (mut deps, mut env) = default_instantiate();
+
+    let owner = deps.api.with_prefix("osmo").addr_make("owner");
+    let guardian = deps.api.with_prefix("osmo").addr_make("guardian");
+    let solver = deps.api.with_prefix("osmo").addr_make("solver");
+
+    let orders = setup(&mut deps, &env, &[1, 2]);
+    let order_ids: Vec<HexBinary> = orders.iter().map(|order| order.id()).collect();
+
+    handle_settlement(
+        &mut deps,
+        &env,
+        order_ids.clone(),
+        HexBinary::from(left_pad_bytes(bech32_decode(solver.as_str()).unwrap(), 32)),
+    );
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(guardian.as_str(), &[]),
+        ExecuteMsg::VetoQueuedSettlement { id: 1 },
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(res, "Caller does not have the guardian role");
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner.as_str(), &[]),
+        ExecuteMsg::GrantRole {
+            role: Role::Guardian,
+            address: guardian.to_string(),
+        },
+    )
+    .unwrap();
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(guardian.as_str(), &[]),
+        ExecuteMsg::VetoQueuedSettlement { id: 1 },
+    )
+    .unwrap();
+
+    env.block.time = env.block.time.plus_seconds(3600);
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("anyone", &[]),
+        ExecuteMsg::ExecuteQueuedSettlement { id: 1 },
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(res, "Queued settlement 1 not found");
+    for order_id in &order_ids {
+        assert_eq!(
+            ORDER_STATUSES
+                .load(deps.as_ref().storage, order_id.to_vec())
+                .unwrap_or_default(),
+            OrderStatus::Unfilled
+        );
+    }
+}
+
+#[test]
+fn test_split_settlements_are_queued_within_delay_window() {
+    let (mut deps, mut env) = default_instantiate();
+
+    let solver = deps.api.with_prefix("osmo").addr_make("solver");
+
+    let orders = setup(&mut deps, &env, &[1, 2, 3]);
+    let repayment_address =
+        HexBinary::from(left_pad_bytes(bech32_decode(solver.as_str()).unwrap(), 32));
+
+    let res = handle_settlement(
+        &mut deps,
+        &env,
+        vec![orders[0].id()],
+        repayment_address.clone(),
+    );
+
+    assert_eq!(res.messages.len(), 1);
+
+    // the second half of the split settlement goes over the threshold
+    env.block.time = env.block.time.plus_seconds(3599);
+
+    let remote_contract = REMOTE_DOMAINS.load(deps.as_ref().storage, 2).unwrap();
+    let (res, status) = receive_command(
+        deps.as_mut(),
+        env.clone(),
+        Transport::Hyperlane,
+        2,
+        &remote_contract,
+        SettleOrdersMessage {
+            order_ids: vec![orders[1].id()],
+            repayment_address: repayment_address.clone(),
+        }
+        .encode(),
+    )
+    .unwrap();
+
+    assert_eq!(status, CommandStatus::Pending);
+    assert!(res.messages.is_empty());
+    assert_eq!(
+        ORDER_STATUSES
+            .load(deps.as_ref().storage, orders[1].id().to_vec())
+            .unwrap_or_default(),
+        OrderStatus::Unfilled
+    );
+
+    // a new window starts once the delay has passed
+    env.block.time = env.block.time.plus_seconds(1);
+
+    let res = handle_settlement(&mut deps, &env, vec![orders[2].id()], repayment_address);
+
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: solver.to_string(),
+            amount: vec![coin(100_000_000, "uusdc")],
+        })]
+    );
+}
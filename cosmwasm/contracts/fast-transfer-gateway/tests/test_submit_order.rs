@@ -1,7 +1,7 @@
-use cosmwasm_std::{coin, testing::mock_info, Attribute, HexBinary, Uint128};
-use go_fast::gateway::ExecuteMsg;
+use cosmwasm_std::{coin, from_json, testing::mock_info, Addr, Attribute, HexBinary, Uint128};
+use go_fast::gateway::{ExecuteMsg, IbcOrigin, QueryMsg};
 use go_fast_transfer_cw::{
-    helpers::{bech32_decode, left_pad_bytes},
+    helpers::{bech32_decode, derive_intermediate_sender, left_pad_bytes},
     msg::SettlementDetails,
     state::SETTLEMENT_DETAILS,
 };
@@ -29,6 +29,7 @@ fn test_submit_order() {
         destination_domain: 2,
         timeout_timestamp: env.block.time.seconds() + 1000,
         data: None,
+        refund_address: None,
     };
 
     let info = mock_info(
@@ -72,6 +73,85 @@ fn test_submit_order() {
             nonce: 1,
             destination_domain: 2,
             amount: Uint128::new(100_000_000),
+            ibc_origin: None,
+            refund_address: None,
+        }
+    );
+}
+
+#[test]
+fn test_ibc_hook_submit_order() {
+    let (mut deps, env) = default_instantiate();
+
+    let ibc_origin = IbcOrigin {
+        channel_id: "channel-0".to_string(),
+        sender: "cosmos1sender".to_string(),
+    };
+
+    let intermediate_sender =
+        derive_intermediate_sender(&ibc_origin.channel_id, &ibc_origin.sender, "osmo").unwrap();
+    let intermediate_sender_hex = HexBinary::from(left_pad_bytes(
+        bech32_decode(intermediate_sender.as_str()).unwrap(),
+        32,
+    ));
+
+    let user_address = deps.api.with_prefix("osmo").addr_make("user");
+    let user_hex = HexBinary::from(left_pad_bytes(
+        bech32_decode(user_address.as_str()).unwrap(),
+        32,
+    ));
+
+    let execute_msg = ExecuteMsg::IbcHookSubmitOrder {
+        ibc_origin: ibc_origin.clone(),
+        recipient: user_hex.clone(),
+        amount_in: Uint128::new(100_000_000),
+        amount_out: Uint128::new(98_000_000),
+        destination_domain: 2,
+        timeout_timestamp: env.block.time.seconds() + 1000,
+        data: None,
+    };
+
+    // only the ibc-hooks intermediary for the origin may submit
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(
+            user_address.as_str(),
+            &[coin(Uint128::new(100_000_000).u128(), "uusdc")],
+        ),
+        execute_msg.clone(),
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(res, "Unauthorized");
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(
+            intermediate_sender.as_str(),
+            &[coin(Uint128::new(100_000_000).u128(), "uusdc")],
+        ),
+        execute_msg,
+    )
+    .unwrap();
+
+    let order_id: HexBinary = res.data.unwrap().into();
+
+    let stored_settlement_details = SETTLEMENT_DETAILS
+        .load(deps.as_ref().storage, order_id.to_vec())
+        .unwrap();
+
+    assert_eq!(
+        stored_settlement_details,
+        SettlementDetails {
+            sender: intermediate_sender_hex,
+            nonce: 1,
+            destination_domain: 2,
+            amount: Uint128::new(100_000_000),
+            ibc_origin: Some(ibc_origin),
+            refund_address: None,
         }
     );
 }
@@ -95,6 +175,7 @@ fn test_submit_order_fails_on_unknown_destination_domain() {
         destination_domain: 3,
         timeout_timestamp: env.block.time.seconds() + 1000,
         data: None,
+        refund_address: None,
     };
 
     let info = mock_info(
@@ -128,6 +209,7 @@ fn test_fill_order_fails_on_incorrect_funds() {
         destination_domain: 2,
         timeout_timestamp: env.block.time.seconds() + 1000,
         data: None,
+        refund_address: None,
     };
 
     let info = mock_info(
@@ -141,3 +223,129 @@ fn test_fill_order_fails_on_incorrect_funds() {
 
     assert_eq!(res, "Unexpected funds sent. Expected: [Coin { 100000000 \"uusdc\" }], Actual: [Coin { 1000000 \"uusdc\" }]");
 }
+
+#[test]
+fn test_submit_order_fails_on_sender_mismatch() {
+    let (mut deps, env) = default_instantiate();
+
+    let user_address = deps.api.with_prefix("osmo").addr_make("user");
+    let other_address = deps.api.with_prefix("osmo").addr_make("other");
+
+    let other_hex = HexBinary::from(left_pad_bytes(
+        bech32_decode(other_address.as_str()).unwrap(),
+        32,
+    ));
+
+    let execute_msg = ExecuteMsg::SubmitOrder {
+        sender: other_hex.clone(),
+        recipient: other_hex.clone(),
+        amount_in: Uint128::new(100_000_000),
+        amount_out: Uint128::new(98_000_000),
+        destination_domain: 2,
+        timeout_timestamp: env.block.time.seconds() + 1000,
+        data: None,
+        refund_address: None,
+    };
+
+    let info = mock_info(
+        user_address.as_str(),
+        &[coin(Uint128::new(100_000_000).u128(), "uusdc")],
+    );
+
+    let res = go_fast_transfer_cw::contract::execute(deps.as_mut(), env.clone(), info, execute_msg)
+        .unwrap_err()
+        .to_string();
+
+    assert_eq!(res, "Order sender does not match caller");
+}
+
+#[test]
+fn test_submit_order_on_behalf_of_sender_by_allowed_submitter() {
+    let (mut deps, env) = default_instantiate();
+
+    let owner = deps.api.with_prefix("osmo").addr_make("owner");
+    let integrator = deps.api.with_prefix("osmo").addr_make("integrator");
+    let user_address = deps.api.with_prefix("osmo").addr_make("user");
+
+    cw_ownable::initialize_owner(&mut deps.storage, &deps.api, Some(owner.as_str())).unwrap();
+
+    let user_hex = HexBinary::from(left_pad_bytes(
+        bech32_decode(user_address.as_str()).unwrap(),
+        32,
+    ));
+
+    let add_msg = ExecuteMsg::AddAllowedSubmitter {
+        address: integrator.to_string(),
+    };
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(integrator.as_str(), &[]),
+        add_msg.clone(),
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(res, "Caller is not the contract's current owner");
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner.as_str(), &[]),
+        add_msg,
+    )
+    .unwrap();
+
+    let allowed_submitters: Vec<Addr> = from_json(
+        go_fast_transfer_cw::contract::query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::AllowedSubmitters {},
+        )
+        .unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(allowed_submitters, vec![integrator.clone()]);
+
+    let execute_msg = ExecuteMsg::SubmitOrder {
+        sender: user_hex.clone(),
+        recipient: user_hex.clone(),
+        amount_in: Uint128::new(100_000_000),
+        amount_out: Uint128::new(98_000_000),
+        destination_domain: 2,
+        timeout_timestamp: env.block.time.seconds() + 1000,
+        data: None,
+        refund_address: None,
+    };
+
+    let info = mock_info(
+        integrator.as_str(),
+        &[coin(Uint128::new(100_000_000).u128(), "uusdc")],
+    );
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        execute_msg.clone(),
+    )
+    .unwrap();
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner.as_str(), &[]),
+        ExecuteMsg::RemoveAllowedSubmitter {
+            address: integrator.to_string(),
+        },
+    )
+    .unwrap();
+
+    let res = go_fast_transfer_cw::contract::execute(deps.as_mut(), env, info, execute_msg)
+        .unwrap_err()
+        .to_string();
+
+    assert_eq!(res, "Order sender does not match caller");
+}
@@ -0,0 +1,252 @@
+use crate::common::default_instantiate;
+use cosmwasm_std::{from_json, testing::mock_info, HexBinary};
+use go_fast::gateway::{ConfigUpdate, ExecuteMsg, Proposal, QueryMsg, Role, TimelockedAction};
+use go_fast_transfer_cw::{
+    contract::DEFAULT_TIMELOCK_DELAY_SECONDS, helpers::left_pad_bytes, state::CONFIG,
+};
+
+pub mod common;
+
+#[test]
+fn test_config_update_waits_for_timelock() {
+    let (mut deps, mut env) = default_instantiate();
+
+    let owner = deps.api.with_prefix("osmo").addr_make("owner");
+
+    cw_ownable::initialize_owner(&mut deps.storage, &deps.api, Some(owner.as_str())).unwrap();
+
+    let hook_addr = deps.api.with_prefix("osmo").addr_make("new_hook");
+
+    let update = ConfigUpdate {
+        hook_addr: Some(hook_addr.to_string()),
+        ..Default::default()
+    };
+
+    let propose_msg = ExecuteMsg::ProposeAction {
+        action: TimelockedAction::UpdateConfig(update.clone()),
+    };
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("not_owner", &[]),
+        propose_msg.clone(),
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(res, "Caller does not have the fee_manager role");
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner.as_str(), &[]),
+        propose_msg,
+    )
+    .unwrap();
+
+    let proposals: Vec<Proposal> = from_json(
+        go_fast_transfer_cw::contract::query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::Proposals {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+
+    let executable_at = env.block.time.plus_seconds(DEFAULT_TIMELOCK_DELAY_SECONDS);
+
+    assert_eq!(
+        proposals,
+        vec![Proposal {
+            id: 1,
+            action: TimelockedAction::UpdateConfig(update),
+            proposer: owner.clone(),
+            executable_at,
+        }]
+    );
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner.as_str(), &[]),
+        ExecuteMsg::ExecuteProposal { id: 1 },
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(
+        res,
+        format!("Proposal not executable until {}", executable_at.seconds())
+    );
+
+    env.block.time = executable_at;
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner.as_str(), &[]),
+        ExecuteMsg::ExecuteProposal { id: 1 },
+    )
+    .unwrap();
+
+    assert_eq!(
+        CONFIG.load(deps.as_ref().storage).unwrap().hook_addr,
+        hook_addr.to_string()
+    );
+
+    let res =
+        go_fast_transfer_cw::contract::query(deps.as_ref(), env, QueryMsg::Proposal { id: 1 });
+
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_guardian_cancels_proposal() {
+    let (mut deps, mut env) = default_instantiate();
+
+    let owner = deps.api.with_prefix("osmo").addr_make("owner");
+    let guardian = deps.api.with_prefix("osmo").addr_make("guardian");
+
+    cw_ownable::initialize_owner(&mut deps.storage, &deps.api, Some(owner.as_str())).unwrap();
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner.as_str(), &[]),
+        ExecuteMsg::GrantRole {
+            role: Role::Guardian,
+            address: guardian.to_string(),
+        },
+    )
+    .unwrap();
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner.as_str(), &[]),
+        ExecuteMsg::ProposeAction {
+            action: TimelockedAction::UpdateTimelockDelay { delay_seconds: 0 },
+        },
+    )
+    .unwrap();
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("not_guardian", &[]),
+        ExecuteMsg::CancelProposal { id: 1 },
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(res, "Caller does not have the guardian role");
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(guardian.as_str(), &[]),
+        ExecuteMsg::CancelProposal { id: 1 },
+    )
+    .unwrap();
+
+    env.block.time = env.block.time.plus_seconds(DEFAULT_TIMELOCK_DELAY_SECONDS);
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner.as_str(), &[]),
+        ExecuteMsg::ExecuteProposal { id: 1 },
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(res, "Proposal 1 not found");
+
+    let timelock_delay: u64 = from_json(
+        go_fast_transfer_cw::contract::query(deps.as_ref(), env, QueryMsg::TimelockDelay {})
+            .unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(timelock_delay, DEFAULT_TIMELOCK_DELAY_SECONDS);
+}
+
+#[test]
+fn test_remote_domain_address_change_requires_proposal() {
+    let (mut deps, mut env) = default_instantiate();
+
+    let owner = deps.api.with_prefix("osmo").addr_make("owner");
+    let manager = deps.api.with_prefix("osmo").addr_make("manager");
+
+    cw_ownable::initialize_owner(&mut deps.storage, &deps.api, Some(owner.as_str())).unwrap();
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner.as_str(), &[]),
+        ExecuteMsg::GrantRole {
+            role: Role::DomainManager,
+            address: manager.to_string(),
+        },
+    )
+    .unwrap();
+
+    let new_address = HexBinary::from(left_pad_bytes(vec![2; 20], 32));
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(manager.as_str(), &[]),
+        ExecuteMsg::AddRemoteDomain {
+            domain: 2,
+            address: new_address.clone(),
+        },
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(
+        res,
+        "Changing a remote domain address requires a timelock proposal"
+    );
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(manager.as_str(), &[]),
+        ExecuteMsg::ProposeAction {
+            action: TimelockedAction::SetRemoteDomain {
+                domain: 2,
+                address: new_address.clone(),
+            },
+        },
+    )
+    .unwrap();
+
+    env.block.time = env.block.time.plus_seconds(DEFAULT_TIMELOCK_DELAY_SECONDS);
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(manager.as_str(), &[]),
+        ExecuteMsg::ExecuteProposal { id: 1 },
+    )
+    .unwrap();
+
+    let remote_domain: HexBinary = from_json(
+        go_fast_transfer_cw::contract::query(
+            deps.as_ref(),
+            env,
+            QueryMsg::RemoteDomain { domain: 2 },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(remote_domain, new_address);
+}
@@ -0,0 +1,419 @@
+use crate::common::default_instantiate;
+use common::submit_order;
+use cosmwasm_std::{
+    coin, from_json, testing::mock_info, Addr, Coin, Event, HexBinary, IbcMsg, IbcTimeout, SubMsg,
+    Uint128,
+};
+use go_fast::{
+    gateway::{ExecuteMsg, QueryMsg, TimelockedAction, Transport},
+    helpers::keccak256_hash,
+    FastTransferOrder,
+};
+use go_fast_transfer_cw::{
+    contract::IBC_SETTLEMENT_TIMEOUT_SECONDS,
+    helpers::{bech32_decode, bech32_encode, left_pad_bytes},
+    msg::{OrderStatus, SettleOrdersMessage},
+    state::{ibc_channel_domain, ORDER_STATUSES, REMOTE_DOMAINS, TIMELOCK_DELAY},
+};
+use hyperlane::message_recipient::HandleMsg;
+
+pub mod common;
+
+fn set_transport(
+    deps: &mut cosmwasm_std::OwnedDeps<
+        cosmwasm_std::MemoryStorage,
+        cosmwasm_std::testing::MockApi,
+        cosmwasm_std::testing::MockQuerier,
+    >,
+    env: &cosmwasm_std::Env,
+    owner: &Addr,
+    transport: Transport,
+) {
+    TIMELOCK_DELAY.save(deps.as_mut().storage, &0).unwrap();
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner.as_str(), &[]),
+        ExecuteMsg::ProposeAction {
+            action: TimelockedAction::SetRemoteDomainTransport {
+                domain: 2,
+                transport,
+            },
+        },
+    )
+    .unwrap();
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner.as_str(), &[]),
+        ExecuteMsg::ExecuteProposal { id: 1 },
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_mock_transport() {
+    let (mut deps, env) = default_instantiate();
+
+    let owner = deps.api.with_prefix("osmo").addr_make("owner");
+    let relayer = deps.api.with_prefix("osmo").addr_make("relayer");
+    let user_address = deps.api.with_prefix("osmo").addr_make("user");
+
+    cw_ownable::initialize_owner(&mut deps.storage, &deps.api, Some(owner.as_str())).unwrap();
+
+    set_transport(
+        &mut deps,
+        &env,
+        &owner,
+        Transport::Mock {
+            relayer: relayer.to_string(),
+        },
+    );
+
+    let user_hex = HexBinary::from(left_pad_bytes(
+        bech32_decode(user_address.as_str()).unwrap(),
+        32,
+    ));
+
+    let order = FastTransferOrder {
+        sender: user_hex.clone(),
+        recipient: user_hex,
+        amount_in: Uint128::new(100_000_000),
+        amount_out: Uint128::new(98_000_000),
+        nonce: 1,
+        source_domain: 1,
+        destination_domain: 2,
+        timeout_timestamp: env.block.time.seconds() + 1000,
+        data: None,
+    };
+
+    submit_order(
+        deps.as_mut(),
+        &env,
+        &mock_info(user_address.as_str(), &[coin(100_000_000, "uusdc")]),
+        &order,
+    )
+    .unwrap();
+
+    let remote_contract = REMOTE_DOMAINS.load(deps.as_ref().storage, 2).unwrap();
+    let body = SettleOrdersMessage {
+        order_ids: vec![order.id()],
+        repayment_address: HexBinary::from(left_pad_bytes(vec![9; 20], 32)),
+    }
+    .encode();
+
+    // commands for a mock domain are no longer accepted from the mailbox
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(
+            &bech32_encode(
+                "osmo",
+                &keccak256_hash("mailbox_contract_address".as_bytes()),
+            )
+            .unwrap()
+            .into_string(),
+            &[],
+        ),
+        ExecuteMsg::Handle(HandleMsg {
+            origin: 2,
+            sender: remote_contract.clone(),
+            body: body.clone(),
+        }),
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(res, "Unauthorized");
+
+    let receive_msg = ExecuteMsg::ReceiveCommand {
+        origin: 2,
+        sender: remote_contract,
+        body,
+    };
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("not_relayer", &[]),
+        receive_msg.clone(),
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(res, "Unauthorized");
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(relayer.as_str(), &[]),
+        receive_msg,
+    )
+    .unwrap();
+
+    assert_eq!(
+        ORDER_STATUSES
+            .load(deps.as_ref().storage, order.id().to_vec())
+            .unwrap(),
+        OrderStatus::Filled
+    );
+
+    let incoming_order = FastTransferOrder {
+        nonce: 2,
+        source_domain: 2,
+        destination_domain: 1,
+        timeout_timestamp: env.block.time.seconds() - 1,
+        ..order
+    };
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("anyone", &[]),
+        ExecuteMsg::InitiateTimeout {
+            orders: vec![incoming_order.clone()],
+        },
+    )
+    .unwrap();
+
+    assert!(res.messages.is_empty());
+    assert_eq!(
+        res.events,
+        vec![Event::new("mock_dispatch")
+            .add_attribute("domain", "2")
+            .add_attribute(
+                "recipient",
+                REMOTE_DOMAINS
+                    .load(deps.as_ref().storage, 2)
+                    .unwrap()
+                    .to_hex()
+            )
+            .add_attribute(
+                "body",
+                go_fast_transfer_cw::msg::TimeoutOrdersMessage {
+                    order_ids: vec![incoming_order.id()],
+                }
+                .encode()
+                .to_hex()
+            )]
+    );
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env,
+        mock_info("anyone", &[coin(1, "uosmo")]),
+        ExecuteMsg::InitiateTimeout {
+            orders: vec![incoming_order],
+        },
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(
+        res,
+        "Unexpected funds sent. Expected: [], Actual: [Coin { 1 \"uosmo\" }]"
+    );
+}
+
+#[test]
+fn test_ibc_transport_dispatch() {
+    let (mut deps, env) = default_instantiate();
+
+    let owner = deps.api.with_prefix("osmo").addr_make("owner");
+    let user_address = deps.api.with_prefix("osmo").addr_make("user");
+    let solver = deps.api.with_prefix("osmo").addr_make("solver");
+
+    cw_ownable::initialize_owner(&mut deps.storage, &deps.api, Some(owner.as_str())).unwrap();
+
+    set_transport(
+        &mut deps,
+        &env,
+        &owner,
+        Transport::Ibc {
+            channel_id: "channel-7".to_string(),
+        },
+    );
+
+    let user_hex = HexBinary::from(left_pad_bytes(
+        bech32_decode(user_address.as_str()).unwrap(),
+        32,
+    ));
+
+    let order = FastTransferOrder {
+        sender: user_hex.clone(),
+        recipient: user_hex,
+        amount_in: Uint128::new(100_000_000),
+        amount_out: Uint128::new(98_000_000),
+        nonce: 1,
+        source_domain: 2,
+        destination_domain: 1,
+        timeout_timestamp: env.block.time.seconds() + 1000,
+        data: None,
+    };
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(solver.as_str(), &[coin(98_000_000, "uusdc")]),
+        ExecuteMsg::FillOrder {
+            filler: solver.clone(),
+            order: order.clone(),
+        },
+    )
+    .unwrap();
+
+    let repayment_address =
+        HexBinary::from(left_pad_bytes(bech32_decode(solver.as_str()).unwrap(), 32));
+
+    let quote: Vec<Coin> = from_json(
+        go_fast_transfer_cw::contract::query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::QuoteInitiateSettlement {
+                order_ids: vec![order.id()],
+                repayment_address: repayment_address.clone(),
+                source_domain: 2,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+
+    assert!(quote.is_empty());
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(solver.as_str(), &[]),
+        ExecuteMsg::InitiateSettlement {
+            order_ids: vec![order.id()],
+            repayment_address: repayment_address.clone(),
+        },
+    )
+    .unwrap();
+
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(IbcMsg::SendPacket {
+            channel_id: "channel-7".to_string(),
+            data: SettleOrdersMessage {
+                order_ids: vec![order.id()],
+                repayment_address,
+            }
+            .encode()
+            .into(),
+            timeout: IbcTimeout::with_timestamp(
+                env.block.time.plus_seconds(IBC_SETTLEMENT_TIMEOUT_SECONDS)
+            ),
+        })]
+    );
+}
+
+#[test]
+fn test_ibc_channel_bound_to_one_domain() {
+    let (mut deps, env) = default_instantiate();
+
+    let owner = deps.api.with_prefix("osmo").addr_make("owner");
+
+    cw_ownable::initialize_owner(&mut deps.storage, &deps.api, Some(owner.as_str())).unwrap();
+
+    set_transport(
+        &mut deps,
+        &env,
+        &owner,
+        Transport::Ibc {
+            channel_id: "channel-0".to_string(),
+        },
+    );
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner.as_str(), &[]),
+        ExecuteMsg::AddRemoteDomain {
+            domain: 3,
+            address: HexBinary::from(left_pad_bytes(vec![3; 20], 32)),
+        },
+    )
+    .unwrap();
+
+    let propose = |domain, channel_id: &str| ExecuteMsg::ProposeAction {
+        action: TimelockedAction::SetRemoteDomainTransport {
+            domain,
+            transport: Transport::Ibc {
+                channel_id: channel_id.to_string(),
+            },
+        },
+    };
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner.as_str(), &[]),
+        propose(3, "channel-0"),
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(
+        res,
+        "Channel channel-0 already carries the commands of domain 2"
+    );
+
+    // both proposals are valid until one of them binds the channel
+    for (domain, channel_id) in [(3, "channel-1"), (2, "channel-1")] {
+        go_fast_transfer_cw::contract::execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(owner.as_str(), &[]),
+            propose(domain, channel_id),
+        )
+        .unwrap();
+    }
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner.as_str(), &[]),
+        ExecuteMsg::ExecuteProposal { id: 2 },
+    )
+    .unwrap();
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner.as_str(), &[]),
+        ExecuteMsg::ExecuteProposal { id: 3 },
+    )
+    .unwrap_err()
+    .to_string();
+
+    assert_eq!(
+        res,
+        "Channel channel-1 already carries the commands of domain 3"
+    );
+
+    // moving a domain off a channel frees it
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner.as_str(), &[]),
+        propose(2, "channel-2"),
+    )
+    .unwrap();
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner.as_str(), &[]),
+        ExecuteMsg::ExecuteProposal { id: 4 },
+    )
+    .unwrap();
+
+    let storage = deps.as_ref().storage;
+    assert_eq!(ibc_channel_domain(storage, "channel-0").unwrap(), None);
+    assert_eq!(ibc_channel_domain(storage, "channel-1").unwrap(), Some(3));
+    assert_eq!(ibc_channel_domain(storage, "channel-2").unwrap(), Some(2));
+}
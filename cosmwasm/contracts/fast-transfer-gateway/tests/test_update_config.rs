@@ -0,0 +1,137 @@
+use crate::common::default_instantiate;
+use cosmwasm_std::{testing::mock_info, Event};
+use go_fast::gateway::{ConfigUpdate, ExecuteMsg, TimelockedAction};
+use go_fast_transfer_cw::state::{CONFIG, TIMELOCK_DELAY};
+
+pub mod common;
+
+#[test]
+fn test_partial_config_update() {
+    let (mut deps, env) = default_instantiate();
+
+    let owner = deps.api.with_prefix("osmo").addr_make("owner");
+
+    cw_ownable::initialize_owner(&mut deps.storage, &deps.api, Some(owner.as_str())).unwrap();
+    TIMELOCK_DELAY.save(deps.as_mut().storage, &0).unwrap();
+
+    let old_config = CONFIG.load(deps.as_ref().storage).unwrap();
+
+    go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(owner.as_str(), &[]),
+        ExecuteMsg::ProposeAction {
+            action: TimelockedAction::UpdateConfig(ConfigUpdate {
+                token_denom: Some("ibc/498A0751C798A0D9A389AA3691123DADA57DAA4F".into()),
+                hook_addr: Some(old_config.hook_addr.clone()),
+                ..Default::default()
+            }),
+        },
+    )
+    .unwrap();
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env,
+        mock_info(owner.as_str(), &[]),
+        ExecuteMsg::ExecuteProposal { id: 1 },
+    )
+    .unwrap();
+
+    assert_eq!(
+        res.events,
+        vec![Event::new("update_config")
+            .add_attribute("old_token_denom", "uusdc")
+            .add_attribute(
+                "new_token_denom",
+                "ibc/498A0751C798A0D9A389AA3691123DADA57DAA4F"
+            )]
+    );
+
+    let config = CONFIG.load(deps.as_ref().storage).unwrap();
+
+    assert_eq!(
+        config.token_denom,
+        "ibc/498A0751C798A0D9A389AA3691123DADA57DAA4F"
+    );
+    assert_eq!(config.address_prefix, old_config.address_prefix);
+    assert_eq!(config.mailbox_addr, old_config.mailbox_addr);
+    assert_eq!(config.hook_addr, old_config.hook_addr);
+}
+
+#[test]
+fn test_config_update_validation() {
+    let (mut deps, env) = default_instantiate();
+
+    let owner = deps.api.with_prefix("osmo").addr_make("owner");
+    let not_a_mailbox = deps.api.with_prefix("osmo").addr_make("not_a_mailbox");
+
+    cw_ownable::initialize_owner(&mut deps.storage, &deps.api, Some(owner.as_str())).unwrap();
+
+    let cases = [
+        (
+            ConfigUpdate {
+                token_denom: Some("".into()),
+                ..Default::default()
+            },
+            "Invalid config: token_denom is not a valid denom",
+        ),
+        (
+            ConfigUpdate {
+                token_denom: Some("1usdc".into()),
+                ..Default::default()
+            },
+            "Invalid config: token_denom is not a valid denom",
+        ),
+        (
+            ConfigUpdate {
+                address_prefix: Some("".into()),
+                ..Default::default()
+            },
+            "Invalid config: address_prefix is not a valid bech32 prefix",
+        ),
+        (
+            ConfigUpdate {
+                address_prefix: Some("OSMO".into()),
+                ..Default::default()
+            },
+            "Invalid config: address_prefix is not a valid bech32 prefix",
+        ),
+        (
+            ConfigUpdate {
+                mailbox_addr: Some(not_a_mailbox.to_string()),
+                ..Default::default()
+            },
+            "Invalid config: mailbox_addr does not respond to mailbox queries",
+        ),
+    ];
+
+    for (update, expected) in cases {
+        let res = go_fast_transfer_cw::contract::execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(owner.as_str(), &[]),
+            ExecuteMsg::ProposeAction {
+                action: TimelockedAction::UpdateConfig(update),
+            },
+        )
+        .unwrap_err()
+        .to_string();
+
+        assert_eq!(res, expected);
+    }
+
+    let res = go_fast_transfer_cw::contract::execute(
+        deps.as_mut(),
+        env,
+        mock_info(owner.as_str(), &[]),
+        ExecuteMsg::ProposeAction {
+            action: TimelockedAction::UpdateConfig(ConfigUpdate {
+                hook_addr: Some("".into()),
+                ..Default::default()
+            }),
+        },
+    );
+
+    assert!(res.is_err());
+}
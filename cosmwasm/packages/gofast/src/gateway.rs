@@ -1,5 +1,6 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::{Addr, Coin, HexBinary, Uint128};
+use cosmwasm_std::{Addr, Coin, HexBinary, Timestamp, Uint128};
+use cw_ownable::{cw_ownable_execute, cw_ownable_query};
 
 use crate::FastTransferOrder;
 
@@ -11,10 +12,120 @@ pub struct Config {
     pub hook_addr: String,
 }
 
+/// Partial config update, fields left as `None` are kept as they are.
+#[cw_serde]
+#[derive(Default)]
+pub struct ConfigUpdate {
+    pub token_denom: Option<String>,
+    pub address_prefix: Option<String>,
+    pub mailbox_addr: Option<String>,
+    pub hook_addr: Option<String>,
+}
+
+impl ConfigUpdate {
+    pub fn apply(self, config: Config) -> Config {
+        Config {
+            token_denom: self.token_denom.unwrap_or(config.token_denom),
+            address_prefix: self.address_prefix.unwrap_or(config.address_prefix),
+            mailbox_addr: self.mailbox_addr.unwrap_or(config.mailbox_addr),
+            hook_addr: self.hook_addr.unwrap_or(config.hook_addr),
+        }
+    }
+}
+
 #[cw_serde]
 pub struct RemoteDomain {
     pub domain: u32,
     pub address: HexBinary,
+    pub enabled: bool,
+    pub rotation: Option<RemoteAddressRotation>,
+    pub transport: Transport,
+    pub attestation: Option<DualAttestation>,
+    pub settlement_delay: Option<SettlementDelay>,
+    pub ism: Option<Addr>,
+}
+
+/// How settlement and timeout commands are carried to and from a remote
+/// domain. The command encoding is the same for every transport.
+#[cw_serde]
+#[derive(Default)]
+pub enum Transport {
+    #[default]
+    Hyperlane,
+    /// A channel opened directly between this gateway and the remote one.
+    Ibc { channel_id: String },
+    /// Outgoing commands are only emitted as events and incoming ones are
+    /// delivered by `relayer` through `ReceiveCommand`. Meant for tests and
+    /// local networks.
+    Mock { relayer: String },
+}
+
+/// Opt-in second transport for a remote domain. Settlements and timeouts to
+/// and from the domain are sent over both transports, and a received command
+/// is only executed once the same payload has arrived over each of them.
+#[cw_serde]
+pub struct DualAttestation {
+    pub transport: Transport,
+    /// How long a command that arrived over one transport waits for the
+    /// other before it has to be attested again.
+    pub pending_expiry_seconds: u64,
+}
+
+/// A settlement or timeout that arrived over one transport of a dual attested
+/// domain and is waiting for the same payload over the other.
+#[cw_serde]
+pub struct PendingAttestation {
+    pub domain: u32,
+    pub payload_hash: HexBinary,
+    pub transport: Transport,
+    pub expires_at: Timestamp,
+}
+
+/// Settlements from a remote domain that would take the amount paid out to it
+/// within `delay_seconds` over `threshold` are queued for `delay_seconds`,
+/// during which a guardian can veto them.
+#[cw_serde]
+pub struct SettlementDelay {
+    pub threshold: Uint128,
+    pub delay_seconds: u64,
+}
+
+#[cw_serde]
+pub struct QueuedSettlement {
+    pub id: u64,
+    pub domain: u32,
+    pub order_ids: Vec<HexBinary>,
+    pub repayment_address: HexBinary,
+    pub amount: Uint128,
+    pub executable_at: Timestamp,
+}
+
+/// The address a remote domain was rotated away from, still accepted for
+/// incoming messages until `expires_at` so in-flight settlements can land.
+#[cw_serde]
+pub struct RemoteAddressRotation {
+    pub previous_address: HexBinary,
+    pub expires_at: Timestamp,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub enum OrderStatus {
+    #[default]
+    Unfilled,
+    Filled,
+    Refunded,
+}
+
+#[cw_serde]
+pub struct OrderStatusResponse {
+    pub order_id: HexBinary,
+    pub status: OrderStatus,
+    pub sender: HexBinary,
+    pub destination_domain: u32,
+    pub amount: Uint128,
+    pub refund_address: Option<Addr>,
+    pub ibc_origin: Option<IbcOrigin>,
 }
 
 #[cw_serde]
@@ -22,6 +133,126 @@ pub struct OrderFill {
     pub order_id: HexBinary,
     pub filler: Addr,
     pub source_domain: u32,
+    #[serde(default)]
+    pub settlement_status: FillSettlementStatus,
+}
+
+/// Progress of the settlement of a fill. Only tracked for domains settling
+/// over IBC, where the remote gateway acknowledges each settlement; fills on
+/// other transports stay `Unsettled`.
+#[cw_serde]
+#[derive(Default)]
+pub enum FillSettlementStatus {
+    #[default]
+    Unsettled,
+    Pending,
+    /// Acknowledged by the remote gateway, which holds the settlement until
+    /// it has been attested over its other transport or its queue delay has
+    /// passed. Stays in flight, settling it again would queue it twice.
+    Accepted,
+    Settled,
+}
+
+/// Origin of an order submitted through an ibc-hooks memo: the local channel
+/// the ICS-20 packet arrived on and the sender on the counterparty chain.
+#[cw_serde]
+pub struct IbcOrigin {
+    pub channel_id: String,
+    pub sender: String,
+}
+
+/// Administrative roles the owner can delegate. The owner implicitly holds
+/// every role.
+#[cw_serde]
+pub enum Role {
+    /// Manages the remote domains orders can be sent to and settled from.
+    DomainManager,
+    /// Manages the Hyperlane hook that quotes and charges dispatch fees.
+    FeeManager,
+    /// Can pause order submission and fills in an emergency.
+    Guardian,
+    /// Manages the settlement delays that rate limit payouts per domain.
+    RateLimitManager,
+}
+
+impl Role {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::DomainManager => "domain_manager",
+            Role::FeeManager => "fee_manager",
+            Role::Guardian => "guardian",
+            Role::RateLimitManager => "rate_limit_manager",
+        }
+    }
+}
+
+#[cw_serde]
+pub struct RoleGrant {
+    pub role: Role,
+    pub address: Addr,
+}
+
+/// A retired destination domain. Unfilled orders sent to it before the sunset,
+/// with a nonce below `next_nonce`, can be reclaimed on this gateway once they
+/// have timed out and `reclaimable_at` has passed.
+#[cw_serde]
+pub struct DomainSunset {
+    pub domain: u32,
+    pub sunset_at: Timestamp,
+    pub reclaimable_at: Timestamp,
+    pub next_nonce: u32,
+}
+
+/// Administrative changes that can move locked funds, and so only take
+/// effect once the timelock delay has passed after they are proposed.
+#[cw_serde]
+pub enum TimelockedAction {
+    UpdateConfig(ConfigUpdate),
+    SetRemoteDomain {
+        domain: u32,
+        address: HexBinary,
+    },
+    /// Removes a remote domain along with its transport, attestation,
+    /// settlement delay and ISM settings. It can only be added back through
+    /// `SetRemoteDomain`.
+    RemoveRemoteDomain {
+        domain: u32,
+    },
+    UpdateTimelockDelay {
+        delay_seconds: u64,
+    },
+    UpdateRotationGracePeriod {
+        grace_period_seconds: u64,
+    },
+    SetRemoteDomainTransport {
+        domain: u32,
+        transport: Transport,
+    },
+    SetRemoteDomainAttestation {
+        domain: u32,
+        attestation: Option<DualAttestation>,
+    },
+    SetSettlementDelay {
+        domain: u32,
+        settlement_delay: Option<SettlementDelay>,
+    },
+    /// Sets the interchain security module verifying messages from domains
+    /// without an override. `None` falls back to the mailbox default.
+    SetInterchainSecurityModule {
+        ism: Option<String>,
+    },
+    SetRemoteDomainIsm {
+        domain: u32,
+        ism: Option<String>,
+    },
+}
+
+#[cw_serde]
+pub struct Proposal {
+    pub id: u64,
+    pub action: TimelockedAction,
+    pub proposer: Addr,
+    pub executable_at: Timestamp,
 }
 
 #[cw_serde]
@@ -31,8 +262,10 @@ pub struct InstantiateMsg {
     pub mailbox_addr: String,
     pub hook_addr: String,
     pub local_domain: u32,
+    pub timelock_delay_seconds: u64,
 }
 
+#[cw_ownable_execute]
 #[cw_serde]
 pub enum ExecuteMsg {
     FillOrder {
@@ -46,13 +279,57 @@ pub enum ExecuteMsg {
     InitiateTimeout {
         orders: Vec<FastTransferOrder>,
     },
-    UpdateConfig {
-        config: Config,
+    ProposeAction {
+        action: TimelockedAction,
+    },
+    ExecuteProposal {
+        id: u64,
+    },
+    CancelProposal {
+        id: u64,
     },
     AddRemoteDomain {
         domain: u32,
         address: HexBinary,
     },
+    SetRemoteDomainEnabled {
+        domain: u32,
+        enabled: bool,
+    },
+    SunsetRemoteDomain {
+        domain: u32,
+    },
+    ReclaimOrders {
+        orders: Vec<FastTransferOrder>,
+    },
+    /// Sends the refunds credited to the sender after their IBC transfer
+    /// failed to `recipient`. Meant to be called through ibc-hooks by the
+    /// origin sender of an order.
+    ClaimRefunds {
+        recipient: String,
+    },
+    ExecuteQueuedSettlement {
+        id: u64,
+    },
+    VetoQueuedSettlement {
+        id: u64,
+    },
+    AddAllowedSubmitter {
+        address: String,
+    },
+    RemoveAllowedSubmitter {
+        address: String,
+    },
+    GrantRole {
+        role: Role,
+        address: String,
+    },
+    RevokeRole {
+        role: Role,
+        address: String,
+    },
+    Pause {},
+    Unpause {},
     SubmitOrder {
         sender: HexBinary,
         recipient: HexBinary,
@@ -61,10 +338,47 @@ pub enum ExecuteMsg {
         destination_domain: u32,
         timeout_timestamp: u64,
         data: Option<HexBinary>,
+        refund_address: Option<String>,
+    },
+    IbcHookSubmitOrder {
+        ibc_origin: IbcOrigin,
+        recipient: HexBinary,
+        amount_in: Uint128,
+        amount_out: Uint128,
+        destination_domain: u32,
+        timeout_timestamp: u64,
+        data: Option<HexBinary>,
     },
     Handle(hyperlane::message_recipient::HandleMsg),
+    ReceiveCommand {
+        origin: u32,
+        sender: HexBinary,
+        body: HexBinary,
+    },
 }
 
+/// Callbacks delivered by the ibc-hooks module for IBC transfers sent by the
+/// gateway with an `ibc_callback` memo.
+#[cw_serde]
+pub enum SudoMsg {
+    #[serde(rename = "ibc_lifecycle_complete")]
+    IbcLifecycleComplete(IbcLifecycleComplete),
+}
+
+#[cw_serde]
+pub enum IbcLifecycleComplete {
+    #[serde(rename = "ibc_ack")]
+    IbcAck {
+        channel: String,
+        sequence: u64,
+        ack: String,
+        success: bool,
+    },
+    #[serde(rename = "ibc_timeout")]
+    IbcTimeout { channel: String, sequence: u64 },
+}
+
+#[cw_ownable_query]
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
@@ -74,15 +388,67 @@ pub enum QueryMsg {
     #[returns(u32)]
     LocalDomain {},
 
+    #[returns(u32)]
+    NextNonce {},
+
     #[returns(HexBinary)]
     RemoteDomain { domain: u32 },
 
     #[returns(Vec<RemoteDomain>)]
     RemoteDomains {},
 
+    #[returns(u64)]
+    RotationGracePeriod {},
+
+    #[returns(Vec<DomainSunset>)]
+    DomainSunsets {},
+
+    #[returns(Vec<Coin>)]
+    ClaimableRefunds { address: String },
+
+    #[returns(QueuedSettlement)]
+    QueuedSettlement { id: u64 },
+
+    #[returns(Vec<QueuedSettlement>)]
+    QueuedSettlements {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    #[returns(Vec<PendingAttestation>)]
+    PendingAttestations {
+        domain: u32,
+        start_after: Option<HexBinary>,
+        limit: Option<u32>,
+    },
+
+    #[returns(Vec<Addr>)]
+    AllowedSubmitters {},
+
+    #[returns(Vec<RoleGrant>)]
+    Roles { role: Option<Role> },
+
+    #[returns(bool)]
+    Paused {},
+
+    #[returns(u64)]
+    TimelockDelay {},
+
+    #[returns(Proposal)]
+    Proposal { id: u64 },
+
+    #[returns(Vec<Proposal>)]
+    Proposals {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
     #[returns(OrderFill)]
     OrderFill { order_id: HexBinary },
 
+    #[returns(OrderStatusResponse)]
+    OrderStatus { order_id: HexBinary },
+
     #[returns(Vec<OrderFill>)]
     OrderFillsByFiller {
         filler: Addr,
@@ -96,4 +462,17 @@ pub enum QueryMsg {
         repayment_address: HexBinary,
         source_domain: u32,
     },
+
+    /// Queried by the mailbox to pick the module verifying messages for the
+    /// gateway. Once a remote domain has an ISM override the gateway answers
+    /// with itself and routes each message by its origin domain.
+    #[returns(hyperlane::ism::InterchainSecurityModuleResponse)]
+    IsmSpecifier(hyperlane::ism::IsmSpecifierQueryMsg),
+
+    /// `module_type` is answered with a `ModuleTypeResponse`.
+    #[returns(hyperlane::ism::VerifyResponse)]
+    Ism(hyperlane::ism::IsmQueryMsg),
+
+    #[returns(hyperlane::ism::RouteResponse)]
+    RoutingIsm(hyperlane::ism::RoutingIsmQueryMsg),
 }
@@ -4,6 +4,7 @@ use helpers::keccak256_hash;
 
 pub mod gateway;
 pub mod helpers;
+pub mod receiver;
 
 #[cw_serde]
 pub struct FastTransferOrder {
@@ -129,4 +130,40 @@ mod tests {
 
         assert_eq!(order, decoded);
     }
+
+    // Shared with `OrderEncoderTest` in solidity/test/OrderEncoder.t.sol, so both
+    // gateways are guaranteed to derive the same order IDs.
+    #[test]
+    fn test_order_encoding_matches_solidity_order_encoder() {
+        let order = FastTransferOrder {
+            sender: keccak256_hash("order_sender".as_bytes()),
+            recipient: keccak256_hash("order_recipient".as_bytes()),
+            amount_in: Uint128::new(1_000000),
+            amount_out: Uint128::new(2_000000),
+            nonce: 5,
+            source_domain: 1,
+            destination_domain: 2,
+            timeout_timestamp: 1234567890,
+            data: Some(HexBinary::from("order_data".as_bytes())),
+        };
+
+        assert_eq!(
+            HexBinary::from(order.clone()).to_hex(),
+            concat!(
+                "d6e9fe1e13a15fa49f0b31e2a6e2365fcbe1a571a8ce319573a5435b672ed419",
+                "d5d52f3cee0c7e1e8825e1d03790c89811cf9daa721cd6cead5a5774ac682ed8",
+                "00000000000000000000000000000000000000000000000000000000000f4240",
+                "00000000000000000000000000000000000000000000000000000000001e8480",
+                "00000005",
+                "00000001",
+                "00000002",
+                "00000000499602d2",
+                "6f726465725f64617461",
+            )
+        );
+        assert_eq!(
+            order.id().to_hex(),
+            "14c730882cf88fb2db99ee836557262c28a9d9fa9b7b90d6c2da63a51daa1b39"
+        );
+    }
 }
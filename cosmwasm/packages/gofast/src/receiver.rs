@@ -0,0 +1,45 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{to_json_binary, Binary, HexBinary, StdResult, Uint128};
+
+/// Actions the destination gateway understands when they are placed in
+/// `FastTransferOrder.data`. Order data that does not decode into a
+/// `FillAction` is passed to the recipient verbatim as a wasm execute message.
+#[cw_serde]
+pub enum FillAction {
+    /// Execute the recipient contract with a `GoFastReceiveMsg` wrapping `payload`.
+    GoFastReceive { payload: Binary },
+    /// Forward the filled funds to `receiver` over the ICS-20 `channel_id`. If
+    /// the transfer is acknowledged with an error or times out, the funds are
+    /// sent to the order recipient on the destination domain instead.
+    IbcTransfer {
+        channel_id: String,
+        receiver: String,
+        timeout_seconds: u64,
+        memo: Option<String>,
+    },
+}
+
+/// Message sent to recipient contracts that opted into the `GoFastReceive`
+/// fill action, so they can authenticate the cross-chain call.
+#[cw_serde]
+pub struct GoFastReceiveMsg {
+    pub order_id: HexBinary,
+    pub sender: HexBinary,
+    pub source_domain: u32,
+    pub amount: Uint128,
+    pub denom: String,
+    pub payload: Binary,
+}
+
+impl GoFastReceiveMsg {
+    /// Serializes the message wrapped in the `go_fast_receive` execute variant.
+    pub fn into_json_binary(self) -> StdResult<Binary> {
+        to_json_binary(&ReceiverExecuteMsg::GoFastReceive(self))
+    }
+}
+
+/// Execute message recipient contracts must accept to opt into `GoFastReceive`.
+#[cw_serde]
+pub enum ReceiverExecuteMsg {
+    GoFastReceive(GoFastReceiveMsg),
+}
@@ -0,0 +1,88 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Addr, Deps, HexBinary, StdResult};
+
+#[cw_serde]
+#[repr(u32)]
+pub enum IsmType {
+    Unused,
+    Routing,
+    Aggregation,
+    LegacyMultisig,
+    MerkleRootMultisig,
+    MessageIdMultisig,
+    Null,
+    CcipRead,
+}
+
+/// Queries every interchain security module answers, wrapped in
+/// `ExpectedIsmQueryMsg`.
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum IsmQueryMsg {
+    #[returns(ModuleTypeResponse)]
+    ModuleType {},
+
+    #[returns(VerifyResponse)]
+    Verify {
+        metadata: HexBinary,
+        message: HexBinary,
+    },
+}
+
+#[cw_serde]
+pub enum ExpectedIsmQueryMsg {
+    Ism(IsmQueryMsg),
+}
+
+#[cw_serde]
+pub struct ModuleTypeResponse {
+    #[serde(rename = "type")]
+    pub typ: IsmType,
+}
+
+#[cw_serde]
+pub struct VerifyResponse {
+    pub verified: bool,
+}
+
+/// Answered by routing modules, which delegate verification to the module
+/// returned for each message.
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum RoutingIsmQueryMsg {
+    #[returns(RouteResponse)]
+    Route { message: HexBinary },
+}
+
+#[cw_serde]
+pub struct RouteResponse {
+    pub ism: String,
+}
+
+/// Answered by message recipients, the mailbox verifies messages with the
+/// returned module or its default one if there is none.
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum IsmSpecifierQueryMsg {
+    #[returns(InterchainSecurityModuleResponse)]
+    InterchainSecurityModule(),
+}
+
+#[cw_serde]
+pub struct InterchainSecurityModuleResponse {
+    pub ism: Option<Addr>,
+}
+
+pub fn verify(
+    deps: Deps,
+    ism: impl Into<String>,
+    metadata: HexBinary,
+    message: HexBinary,
+) -> StdResult<bool> {
+    let response: VerifyResponse = deps.querier.query_wasm_smart(
+        ism,
+        &ExpectedIsmQueryMsg::Ism(IsmQueryMsg::Verify { metadata, message }),
+    )?;
+
+    Ok(response.verified)
+}
@@ -1,2 +1,4 @@
+pub mod ism;
 pub mod mailbox;
+pub mod message;
 pub mod message_recipient;
@@ -38,6 +38,9 @@ pub enum MailboxQueryMsg {
 
     #[returns(RequiredHookResponse)]
     RequiredHook {},
+
+    #[returns(DefaultIsmResponse)]
+    DefaultIsm {},
 }
 
 #[cw_serde]
@@ -50,6 +53,11 @@ pub struct RequiredHookResponse {
     pub required_hook: String,
 }
 
+#[cw_serde]
+pub struct DefaultIsmResponse {
+    pub default_ism: String,
+}
+
 #[cw_serde]
 pub struct QuoteDispatchResponse {
     pub fees: Vec<Coin>,
@@ -72,6 +80,14 @@ pub fn get_required_hook(deps: Deps, mailbox: impl Into<String>) -> StdResult<St
     Ok(response.required_hook)
 }
 
+pub fn get_default_ism(deps: Deps, mailbox: impl Into<String>) -> StdResult<String> {
+    let response: DefaultIsmResponse = deps
+        .querier
+        .query_wasm_smart(mailbox, &QueryMsg::Mailbox(MailboxQueryMsg::DefaultIsm {}))?;
+
+    Ok(response.default_ism)
+}
+
 pub fn quote_dispatch(
     deps: Deps,
     mailbox: impl Into<String>,
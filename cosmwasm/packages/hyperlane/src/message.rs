@@ -0,0 +1,17 @@
+use cosmwasm_std::{StdError, StdResult};
+
+// version (1) | nonce (4) | origin (4) | sender (32) | destination (4) | recipient (32) | body
+const ORIGIN_OFFSET: usize = 5;
+const HEADER_LENGTH: usize = 77;
+
+/// The origin domain of an encoded Hyperlane message.
+pub fn origin_domain(message: &[u8]) -> StdResult<u32> {
+    if message.len() < HEADER_LENGTH {
+        return Err(StdError::generic_err("Hyperlane message too short"));
+    }
+
+    let mut origin = [0u8; 4];
+    origin.copy_from_slice(&message[ORIGIN_OFFSET..ORIGIN_OFFSET + 4]);
+
+    Ok(u32::from_be_bytes(origin))
+}
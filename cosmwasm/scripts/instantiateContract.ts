@@ -18,6 +18,7 @@ const mailboxAddr =
 const hookAddr =
   "osmo13yswqchwtmv2ln9uz4w3865sfy5k8x0wg9qrv4vxflxjg0kuwwyqqpvqxz";
 const localDomain = 875;
+const timelockDelaySeconds = 24 * 60 * 60;
 
 interface InstantiateMsg {
   token_denom: string;
@@ -25,6 +26,7 @@ interface InstantiateMsg {
   mailbox_addr: string;
   hook_addr: string;
   local_domain: number;
+  timelock_delay_seconds: number;
 }
 
 async function main() {
@@ -50,6 +52,7 @@ async function main() {
     mailbox_addr: mailboxAddr,
     hook_addr: hookAddr,
     local_domain: localDomain,
+    timelock_delay_seconds: timelockDelaySeconds,
   };
 
   const { address } = await instantiateContract(
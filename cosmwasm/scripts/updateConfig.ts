@@ -38,10 +38,11 @@ async function main() {
   //     contract: CONTRACT_ADDRESS,
   //     msg: Buffer.from(
   //       JSON.stringify({
-  //         update_config: {
-  //           config: {
-  //             ...config,
-  //             remote_addr: remoteAddr.replace("0x", ""),
+  //         propose_action: {
+  //           action: {
+  //             update_config: {
+  //               hook_addr: config.hook_addr,
+  //             },
   //           },
   //         },
   //       })
@@ -0,0 +1,47 @@
+// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.13;
+
+import {Test} from "forge-std/Test.sol";
+
+import {FastTransferOrder} from "../src/FastTransferGateway.sol";
+import {OrderEncoder} from "../src/libraries/OrderEncoder.sol";
+
+// Test vectors shared with `test_order_encoding_matches_solidity_order_encoder`
+// in cosmwasm/packages/gofast/src/lib.rs
+contract OrderEncoderTest is Test {
+    function _vectorOrder() internal pure returns (FastTransferOrder memory) {
+        return FastTransferOrder({
+            sender: keccak256("order_sender"),
+            recipient: keccak256("order_recipient"),
+            amountIn: 1_000000,
+            amountOut: 2_000000,
+            nonce: 5,
+            sourceDomain: 1,
+            destinationDomain: 2,
+            timeoutTimestamp: 1234567890,
+            data: bytes("order_data")
+        });
+    }
+
+    function test_encodeMatchesCosmWasmEncoding() public pure {
+        bytes memory expected = bytes.concat(
+            hex"d6e9fe1e13a15fa49f0b31e2a6e2365fcbe1a571a8ce319573a5435b672ed419",
+            hex"d5d52f3cee0c7e1e8825e1d03790c89811cf9daa721cd6cead5a5774ac682ed8",
+            hex"00000000000000000000000000000000000000000000000000000000000f4240",
+            hex"00000000000000000000000000000000000000000000000000000000001e8480",
+            hex"00000005",
+            hex"00000001",
+            hex"00000002",
+            hex"00000000499602d2",
+            hex"6f726465725f64617461"
+        );
+
+        assertEq(OrderEncoder.encode(_vectorOrder()), expected);
+    }
+
+    function test_idMatchesCosmWasmOrderId() public pure {
+        assertEq(
+            OrderEncoder.id(_vectorOrder()), hex"14c730882cf88fb2db99ee836557262c28a9d9fa9b7b90d6c2da63a51daa1b39"
+        );
+    }
+}